@@ -0,0 +1,51 @@
+/* C interface to the wordsearch solver; see src/ffi.rs.
+ *
+ * Handles are owned by the caller and released with the matching *_free
+ * function. Strings are UTF-8. Link against libwordsearch (the cdylib or the
+ * staticlib; the staticlib also needs -lpthread -ldl -lm on Linux).
+ *
+ * This header is maintained by hand, not generated; keep it in step with
+ * src/ffi.rs, whose tests check these prototypes against its signatures. */
+
+#ifndef WORDSEARCH_H
+#define WORDSEARCH_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct Dict ws_dict;
+typedef struct Board ws_board;
+typedef struct Results ws_results;
+
+/* Builds a dictionary from len bytes of newline-separated words. */
+ws_dict *ws_dict_new(const char *words, size_t len);
+void ws_dict_free(ws_dict *dict);
+
+/* Parses a NUL-terminated board of rows separated by spaces. Returns NULL on
+ * failure, writing the reason to error (at most error_len bytes, NUL
+ * included, cut at a UTF-8 character boundary) if it is not NULL. */
+ws_board *ws_board_parse(const char *board, char *error, size_t error_len);
+void ws_board_free(ws_board *board);
+
+/* Finds every path on the board that spells a word in the dictionary. */
+ws_results *ws_search(const ws_board *board, const ws_dict *dict);
+size_t ws_results_len(const ws_results *results);
+
+/* The word of result i, valid until the results are freed; NULL if i is out
+ * of range. */
+const char *ws_results_word(const ws_results *results, size_t i);
+
+/* Copies up to cap cell numbers (row-major, from 0) of result i's path into
+ * cells and returns the path's full length; 0 if i is out of range. */
+size_t ws_results_path(const ws_results *results, size_t i, uint32_t *cells, size_t cap);
+void ws_results_free(ws_results *results);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,345 @@
+use crate::{Board, Path};
+use std::collections::BTreeMap as Map;
+use std::collections::BTreeSet as Set;
+use std::fmt;
+
+// Stats
+
+/// Summary figures for the words found on a board.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub word_count: usize,
+    pub average_length: f64,
+    pub longest: Option<String>,
+    /// The share of words that appear in the common word list, if one was given.
+    pub common_share: Option<f64>,
+}
+
+impl Stats {
+    pub fn new(words: &[String], common: Option<&Set<String>>) -> Stats {
+        let word_count = words.len();
+        let total: usize = words.iter().map(|word| word.chars().count()).sum();
+        let average_length = if word_count == 0 {
+            0.0
+        } else {
+            total as f64 / word_count as f64
+        };
+        // first of the longest, so ties go to the alphabetically earliest
+        let longest = words
+            .iter()
+            .fold(None, |longest: Option<&String>, word| match longest {
+                Some(l) if l.chars().count() >= word.chars().count() => Some(l),
+                _ => Some(word),
+            })
+            .cloned();
+        let common_share = common.map(|common| {
+            if word_count == 0 {
+                0.0
+            } else {
+                words.iter().filter(|word| common.contains(*word)).count() as f64
+                    / word_count as f64
+            }
+        });
+
+        Stats {
+            word_count,
+            average_length,
+            longest,
+            common_share,
+        }
+    }
+}
+
+/// Reads a frequency list, most frequent first, and keeps its first `limit`
+/// words. Each line's first field is the word; anything after it, such as a
+/// count, is ignored.
+pub fn common_words(list: &str, limit: usize) -> Set<String> {
+    list.lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .take(limit)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// Difficulty
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Easy => "easy",
+            Level::Medium => "medium",
+            Level::Hard => "hard",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Combines board stats into a score from 0 (easiest) to 100 (hardest).
+///
+/// Three factors, each scaled to 0..1, are averaged by weight: scarcity (fewer
+/// than `saturation` words), length (average length above 3, capped at 7), and
+/// obscurity (the share of words not in the common list). Obscurity is left out
+/// when the stats have no common share.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difficulty {
+    pub scarcity_weight: f64,
+    pub length_weight: f64,
+    pub obscurity_weight: f64,
+    pub saturation: usize,
+    pub easy_below: f64,
+    pub hard_from: f64,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty {
+            scarcity_weight: 1.0,
+            length_weight: 1.0,
+            obscurity_weight: 1.0,
+            saturation: 100,
+            easy_below: 35.0,
+            hard_from: 65.0,
+        }
+    }
+}
+
+impl Difficulty {
+    pub fn score(&self, stats: &Stats) -> f64 {
+        let found = stats.word_count.min(self.saturation) as f64;
+        let scarcity = 1.0 - found / self.saturation.max(1) as f64;
+        let length = ((stats.average_length - 3.0) / 4.0).clamp(0.0, 1.0);
+
+        let mut factors = vec![
+            (self.scarcity_weight, scarcity),
+            (self.length_weight, length),
+        ];
+        if let Some(share) = stats.common_share {
+            factors.push((self.obscurity_weight, 1.0 - share));
+        }
+
+        let weight: f64 = factors.iter().map(|(w, _)| w).sum();
+        if weight <= 0.0 {
+            return 0.0;
+        }
+        100.0 * factors.iter().map(|(w, x)| w * x).sum::<f64>() / weight
+    }
+
+    pub fn level(&self, score: f64) -> Level {
+        if score < self.easy_below {
+            Level::Easy
+        } else if score < self.hard_from {
+            Level::Medium
+        } else {
+            Level::Hard
+        }
+    }
+
+    pub fn rate(&self, stats: &Stats) -> (Level, f64) {
+        let score = self.score(stats);
+        (self.level(score), score)
+    }
+}
+
+// Grid
+
+/// A value for each cell of a board, in row-major order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    pub width: u32,
+    pub height: u32,
+    pub cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn get(&self, row: u32, col: u32) -> Option<&T> {
+        if row < self.height && col < self.width {
+            self.cells.get((row * self.width + col) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1) as usize)
+    }
+}
+
+impl Grid<u32> {
+    /// Renders the counts as rows of right-aligned numbers, each followed by a
+    /// shade from ` ` (zero) to `@` (the highest count).
+    pub fn heatmap(&self) -> String {
+        const SHADES: [char; 10] = [' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+        let max = self.cells.iter().copied().max().unwrap_or(0);
+        let digits = max.to_string().len();
+        let shade = |n: u32| {
+            if n == 0 {
+                SHADES[0]
+            } else {
+                let top = SHADES.len() as u32 - 1;
+                SHADES[(1 + (n - 1) * (top - 1) / (max - 1).max(1)) as usize]
+            }
+        };
+
+        self.rows()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .map(|&n| format!("{:>width$}{}", n, shade(n), width = digits))
+                    .collect();
+                cells.join(" ") + "\n"
+            })
+            .collect()
+    }
+}
+
+// Usage
+
+/// How productive each cell of a board is: the number of distinct words that
+/// pass through it, and the number that start on it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Usage {
+    pub through: Grid<u32>,
+    pub starts: Grid<u32>,
+}
+
+impl Usage {
+    /// Counts cell usage over `paths`, as returned by `Board::search`. A word
+    /// that can be traced several ways counts once for each cell any of its
+    /// paths visits.
+    pub fn new(board: &Board, paths: &[Path]) -> Usage {
+        let mut words: Map<String, (Set<u32>, Set<u32>)> = Map::new();
+        for path in paths {
+            let (through, starts) = words.entry(board.path_to_word(path)).or_default();
+            through.extend(path.iter().copied());
+            starts.extend(path.first().copied());
+        }
+
+        let size = (board.width() * board.height()) as usize;
+        let mut through = vec![0; size];
+        let mut starts = vec![0; size];
+        for (cells, first) in words.values() {
+            for &pos in cells {
+                through[pos as usize] += 1;
+            }
+            for &pos in first {
+                starts[pos as usize] += 1;
+            }
+        }
+
+        let grid = |cells| Grid {
+            width: board.width(),
+            height: board.height(),
+            cells,
+        };
+        Usage {
+            through: grid(through),
+            starts: grid(starts),
+        }
+    }
+}
+
+// test Stats
+
+#[cfg(test)]
+mod test_stats {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn new() {
+        let common = common_words("the 100\nant 50\nbat 20\nzymurgy 1\n", 3);
+        let stats = Stats::new(&words(&["ant", "bat", "tab", "tabs"]), Some(&common));
+
+        assert_eq!(stats.word_count, 4);
+        assert_eq!(stats.average_length, 3.25);
+        assert_eq!(stats.longest, Some("tabs".to_string()));
+        assert_eq!(stats.common_share, Some(0.5));
+    }
+
+    #[test]
+    fn empty() {
+        let stats = Stats::new(&[], None);
+
+        assert_eq!(stats.word_count, 0);
+        assert_eq!(stats.average_length, 0.0);
+        assert_eq!(stats.longest, None);
+        assert_eq!(stats.common_share, None);
+    }
+}
+
+// test Difficulty
+
+#[cfg(test)]
+mod test_difficulty {
+    use super::*;
+
+    fn stats(word_count: usize, average_length: f64, common_share: Option<f64>) -> Stats {
+        Stats {
+            word_count,
+            average_length,
+            longest: None,
+            common_share,
+        }
+    }
+
+    #[test]
+    fn score() {
+        let difficulty = Difficulty::default();
+
+        assert_eq!(difficulty.score(&stats(100, 3.0, Some(1.0))), 0.0);
+        assert_eq!(difficulty.score(&stats(0, 7.0, Some(0.0))), 100.0);
+        assert_eq!(difficulty.score(&stats(50, 5.0, None)), 50.0);
+    }
+
+    #[test]
+    fn rate() {
+        let difficulty = Difficulty::default();
+
+        assert_eq!(difficulty.rate(&stats(200, 3.5, Some(0.9))).0, Level::Easy);
+        assert_eq!(difficulty.rate(&stats(50, 5.0, None)).0, Level::Medium);
+        assert_eq!(difficulty.rate(&stats(5, 6.0, Some(0.2))).0, Level::Hard);
+    }
+}
+
+// test Usage
+
+#[cfg(test)]
+mod test_usage {
+    use super::*;
+    use crate::Dict;
+
+    #[test]
+    fn new() {
+        let dict: Dict = ["an", "ant", "bat", "tab", "tan"].iter().copied().collect();
+        let board: Board = "ba tn".parse().unwrap();
+
+        let usage = Usage::new(&board, &board.search(&dict));
+
+        assert_eq!(usage.through.cells, vec![2, 5, 4, 3]);
+        assert_eq!(usage.starts.cells, vec![1, 2, 2, 0]);
+        assert_eq!(usage.through.get(1, 0), Some(&4));
+        assert_eq!(usage.through.get(2, 0), None);
+    }
+
+    #[test]
+    fn heatmap() {
+        let grid = Grid {
+            width: 2,
+            height: 2,
+            cells: vec![0, 10, 5, 1],
+        };
+
+        assert_eq!(grid.heatmap(), " 0  10@\n 5=  1.\n");
+    }
+}
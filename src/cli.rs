@@ -0,0 +1,162 @@
+use std::collections::BTreeMap as Map;
+use std::collections::BTreeSet as Set;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+// Args
+
+/// Command-line arguments split into positionals, switches and options.
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub positional: Vec<String>,
+    switches: Set<String>,
+    values: Map<String, String>,
+}
+
+impl Args {
+    /// Parses `args` given the names of the options that take a value and the
+    /// switches that don't. `--help` and `-h` are always accepted as `--help`.
+    pub fn parse<I>(args: I, options: &[&str], switches: &[&str]) -> Result<Args, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Args {
+            positional: vec![],
+            switches: Set::new(),
+            values: Map::new(),
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let name = arg.as_str();
+            if name == "--help" || name == "-h" {
+                parsed.switches.insert("--help".to_string());
+            } else if options.contains(&name) {
+                let value = args.next().ok_or(format!("{} needs a value", name))?;
+                parsed.values.insert(arg, value);
+            } else if switches.contains(&name) {
+                parsed.switches.insert(arg);
+            } else if name.starts_with("--") {
+                return Err(format!("unknown option: {}", name));
+            } else {
+                parsed.positional.push(arg);
+            }
+        }
+        Ok(parsed)
+    }
+
+    pub fn has(&self, switch: &str) -> bool {
+        self.switches.contains(switch)
+    }
+
+    pub fn value(&self, option: &str) -> Option<&str> {
+        self.values.get(option).map(String::as_str)
+    }
+
+    pub fn parsed<T: FromStr>(&self, option: &str) -> Result<Option<T>, String> {
+        match self.value(option) {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid value for {}: {}", option, value)),
+            None => Ok(None),
+        }
+    }
+}
+
+// Lines
+
+/// Input lines read as raw bytes, so that one line that isn't UTF-8 doesn't end
+/// the stream. Each item is the line's number and its text, or its lossily
+/// decoded text if it isn't UTF-8. Line endings are stripped.
+pub struct Lines<R> {
+    input: R,
+    number: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(input: R) -> Self {
+        Lines { input, number: 0 }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<(usize, Result<String, String>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = vec![];
+        match self.input.read_until(b'\n', &mut bytes) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(error) => return Some(Err(error)),
+        }
+        if bytes.last() == Some(&b'\n') {
+            bytes.pop();
+            if bytes.last() == Some(&b'\r') {
+                bytes.pop();
+            }
+        }
+        self.number += 1;
+        let line = String::from_utf8(bytes)
+            .map_err(|error| String::from_utf8_lossy(error.as_bytes()).into_owned());
+        Some(Ok((self.number, line)))
+    }
+}
+
+// test Args
+
+#[cfg(test)]
+mod test_args {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse() {
+        let parsed = Args::parse(
+            args(&["abc def", "--seed", "7", "--void", "-h", "bat"]),
+            &["--seed"],
+            &["--void"],
+        )
+        .unwrap();
+
+        assert_eq!(parsed.positional, args(&["abc def", "bat"]));
+        assert!(parsed.has("--void"));
+        assert!(parsed.has("--help"));
+        assert_eq!(parsed.value("--seed"), Some("7"));
+        assert_eq!(parsed.parsed::<u64>("--seed"), Ok(Some(7)));
+        assert_eq!(parsed.parsed::<u64>("--width"), Ok(None));
+    }
+
+    #[test]
+    fn parse_err() {
+        assert!(Args::parse(args(&["--seed"]), &["--seed"], &[]).is_err());
+        assert!(Args::parse(args(&["--nope"]), &[], &[]).is_err());
+
+        let parsed = Args::parse(args(&["--seed", "x"]), &["--seed"], &[]).unwrap();
+        assert!(parsed.parsed::<u64>("--seed").is_err());
+    }
+}
+
+// test Lines
+
+#[cfg(test)]
+mod test_lines {
+    use super::*;
+
+    #[test]
+    fn lines() {
+        let input: &[u8] = b"ab cd\r\n\xff\xfe xx\nba tn";
+        let lines: Vec<_> = Lines::new(input).map(Result::unwrap).collect();
+        assert_eq!(
+            lines,
+            vec![
+                (1, Ok("ab cd".to_string())),
+                (2, Err("\u{fffd}\u{fffd} xx".to_string())),
+                (3, Ok("ba tn".to_string())),
+            ]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::rng::Rng;
+
+// Dice
+
+/// A set of letter dice, each a list of its faces. A `q` face stands for the
+/// "Qu" tile, so rolled boards are parsed with `ParseOptions::rolled`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dice(pub Vec<Vec<char>>);
+
+impl Dice {
+    /// The 16 dice of the classic 4x4 Boggle set.
+    pub fn classic() -> Dice {
+        Dice::parse(
+            "aaeegn abbjoo achops affkps aoottw cimotu deilrx delrvy \
+             distty eeghnw eeinsu ehrtvw eiosst elrtty himnqu hlnnrz",
+        )
+    }
+
+    /// Reads dice separated by whitespace, each written as its faces.
+    pub fn parse(s: &str) -> Dice {
+        Dice(
+            s.split_whitespace()
+                .map(|die| die.chars().collect())
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Shakes the dice into rows of `width`, returning the board as rows
+    /// separated by spaces, ready to parse. Dice that don't fill a row are
+    /// left out.
+    pub fn roll(&self, rng: &mut Rng, width: usize) -> String {
+        let mut dice: Vec<&Vec<char>> = self.0.iter().collect();
+        for i in (1..dice.len()).rev() {
+            dice.swap(i, rng.below(i + 1));
+        }
+
+        let faces: Vec<char> = dice
+            .iter()
+            .map(|die| rng.choose(die).copied().unwrap_or('?'))
+            .collect();
+        let rows: Vec<String> = faces
+            .chunks(width.max(1))
+            .filter(|row| row.len() == width)
+            .map(|row| row.iter().collect())
+            .collect();
+        rows.join(" ")
+    }
+
+    /// The width of the square board these dice fill, if there is one.
+    pub fn square_width(&self) -> Option<usize> {
+        (1..=self.len()).find(|w| w * w == self.len())
+    }
+}
+
+// test Dice
+
+#[cfg(test)]
+mod test_dice {
+    use super::*;
+
+    #[test]
+    fn classic() {
+        let dice = Dice::classic();
+
+        assert_eq!(dice.len(), 16);
+        assert!(dice.0.iter().all(|die| die.len() == 6));
+        assert_eq!(dice.0[14], vec!['h', 'i', 'm', 'n', 'q', 'u']);
+        assert_eq!(dice.square_width(), Some(4));
+        assert_eq!(Dice::parse("ab cd ef").square_width(), None);
+    }
+
+    #[test]
+    fn roll() {
+        let dice = Dice::classic();
+
+        let board = dice.roll(&mut Rng::new(1), 4);
+        let rows: Vec<&str> = board.split(' ').collect();
+
+        assert_eq!(rows.len(), 4);
+        assert!(rows.iter().all(|row| row.chars().count() == 4));
+        assert_eq!(board, dice.roll(&mut Rng::new(1), 4));
+        assert_ne!(board, dice.roll(&mut Rng::new(2), 4));
+        assert_eq!(Dice::parse("a b c d e").roll(&mut Rng::new(0), 2).len(), 5);
+    }
+}
@@ -1,4 +1,4 @@
-pub const WORDS: [&str; 79339] = [
+pub static WORDS: [&str; 79339] = [
     "aa", "aah", "aahed", "aahing", "aahs", "aal", "aalii", "aaliis", "aals", "aardvark",
     "aardwolf", "aargh", "aarrgh", "aarrghh", "aas", "aasvogel", "aba", "abaca", "abacas", "abaci",
     "aback", "abacus", "abacuses", "abaft", "abaka", "abakas", "abalone", "abalones", "abamp",
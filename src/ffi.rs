@@ -0,0 +1,327 @@
+//! A C interface to the solver, declared in `include/wordsearch.h`.
+//!
+//! Handles returned by the `*_new`, `*_parse` and `ws_search` functions are
+//! owned by the caller and must be released with the matching `*_free`
+//! function. Strings are UTF-8.
+//!
+//! The header is written by hand. When adding a function here, declare it
+//! there too; a test checks that the header's prototypes match the signatures
+//! here.
+
+use crate::{Board, Dict, Path};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+use std::slice;
+
+pub struct Results(Vec<(CString, Path)>);
+
+/// Builds a dictionary from `len` bytes of newline-separated words.
+///
+/// # Safety
+///
+/// `words` must point to `len` readable bytes, or be null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn ws_dict_new(words: *const c_char, len: usize) -> *mut Dict {
+    let bytes: &[u8] = if words.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(words as *const u8, len)
+    };
+    let text = String::from_utf8_lossy(bytes);
+    let dict: Dict = text
+        .lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .collect();
+    Box::into_raw(Box::new(dict))
+}
+
+/// # Safety
+///
+/// `dict` must be null or a handle from `ws_dict_new` not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn ws_dict_free(dict: *mut Dict) {
+    if !dict.is_null() {
+        drop(Box::from_raw(dict));
+    }
+}
+
+/// Parses a NUL-terminated board, as rows separated by spaces. On failure it
+/// returns null and, if `error` is not null, writes the reason there as a
+/// NUL-terminated string of at most `error_len` bytes.
+///
+/// # Safety
+///
+/// `board` must be a NUL-terminated string, and `error` null or writable for
+/// `error_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_board_parse(
+    board: *const c_char,
+    error: *mut c_char,
+    error_len: usize,
+) -> *mut Board {
+    let parsed = if board.is_null() {
+        Err("board is null".to_string())
+    } else {
+        CStr::from_ptr(board)
+            .to_str()
+            .map_err(|_| "board is not UTF-8".to_string())
+            .and_then(|s| s.parse::<Board>().map_err(|e| e.to_string()))
+    };
+
+    match parsed {
+        Ok(board) => Box::into_raw(Box::new(board)),
+        Err(message) => {
+            if !error.is_null() && error_len > 0 {
+                // cut at a character boundary, so the C side gets UTF-8
+                let mut n = message.len().min(error_len - 1);
+                while !message.is_char_boundary(n) {
+                    n -= 1;
+                }
+                ptr::copy_nonoverlapping(message.as_ptr() as *const c_char, error, n);
+                *error.add(n) = 0;
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+///
+/// `board` must be null or a handle from `ws_board_parse` not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn ws_board_free(board: *mut Board) {
+    if !board.is_null() {
+        drop(Box::from_raw(board));
+    }
+}
+
+/// Finds every path on `board` that spells a word in `dict`, as
+/// `Board::search` does. Returns null if either handle is null.
+///
+/// # Safety
+///
+/// `board` and `dict` must be live handles or null.
+#[no_mangle]
+pub unsafe extern "C" fn ws_search(board: *const Board, dict: *const Dict) -> *mut Results {
+    if board.is_null() || dict.is_null() {
+        return ptr::null_mut();
+    }
+    let (board, dict) = (&*board, &*dict);
+    let results = board
+        .search(dict)
+        .into_iter()
+        .map(|path| {
+            let word = CString::new(board.path_to_word(&path)).unwrap_or_default();
+            (word, path)
+        })
+        .collect();
+    Box::into_raw(Box::new(Results(results)))
+}
+
+/// # Safety
+///
+/// `results` must be a live handle or null.
+#[no_mangle]
+pub unsafe extern "C" fn ws_results_len(results: *const Results) -> usize {
+    results.as_ref().map_or(0, |results| results.0.len())
+}
+
+/// The word of result `i`, valid until the results are freed, or null if `i`
+/// is out of range.
+///
+/// # Safety
+///
+/// `results` must be a live handle or null.
+#[no_mangle]
+pub unsafe extern "C" fn ws_results_word(results: *const Results, i: usize) -> *const c_char {
+    match results.as_ref().and_then(|results| results.0.get(i)) {
+        Some((word, _)) => word.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Copies up to `cap` cell numbers of result `i`'s path into `cells`, and
+/// returns the full length of the path, or 0 if `i` is out of range. Cells are
+/// numbered in row-major order from 0.
+///
+/// # Safety
+///
+/// `results` must be a live handle or null, and `cells` null or writable for
+/// `cap` values.
+#[no_mangle]
+pub unsafe extern "C" fn ws_results_path(
+    results: *const Results,
+    i: usize,
+    cells: *mut u32,
+    cap: usize,
+) -> usize {
+    match results.as_ref().and_then(|results| results.0.get(i)) {
+        Some((_, path)) => {
+            if !cells.is_null() {
+                ptr::copy_nonoverlapping(path.as_ptr(), cells, path.len().min(cap));
+            }
+            path.len()
+        }
+        None => 0,
+    }
+}
+
+/// # Safety
+///
+/// `results` must be null or a handle from `ws_search` not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn ws_results_free(results: *mut Results) {
+    if !results.is_null() {
+        drop(Box::from_raw(results));
+    }
+}
+
+// test ffi
+
+#[cfg(test)]
+mod test_ffi {
+    use super::*;
+
+    #[test]
+    fn search() {
+        let words = "an\nant\nbat\ntab\n";
+        let mut error = [0 as c_char; 64];
+
+        unsafe {
+            let dict = ws_dict_new(words.as_ptr() as *const c_char, words.len());
+            let board =
+                ws_board_parse(b"ba tn\0".as_ptr() as *const c_char, error.as_mut_ptr(), 64);
+            let results = ws_search(board, dict);
+
+            assert_eq!(ws_results_len(results), 4);
+            let words: Vec<String> = (0..4)
+                .map(|i| {
+                    CStr::from_ptr(ws_results_word(results, i))
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            let mut sorted = words.clone();
+            sorted.sort();
+            assert_eq!(sorted, vec!["an", "ant", "bat", "tab"]);
+
+            let mut cells = [0u32; 2];
+            let i = words.iter().position(|w| w == "bat").unwrap();
+            assert_eq!(ws_results_path(results, i, cells.as_mut_ptr(), 2), 3);
+            assert_eq!(cells, [0, 1]);
+            assert_eq!(ws_results_word(results, 4), ptr::null());
+
+            ws_results_free(results);
+            ws_board_free(board);
+            ws_dict_free(dict);
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let mut error = [0 as c_char; 16];
+
+        unsafe {
+            let board = ws_board_parse(b"abc\0".as_ptr() as *const c_char, error.as_mut_ptr(), 16);
+
+            assert!(board.is_null());
+            assert_eq!(
+                CStr::from_ptr(error.as_ptr()).to_str(),
+                Ok("must have at le")
+            );
+            assert!(ws_search(board, ptr::null()).is_null());
+            assert_eq!(ws_results_len(ptr::null()), 0);
+        }
+    }
+
+    #[test]
+    fn parse_error_keeps_utf8() {
+        let mut error = [0 as c_char; 21];
+
+        unsafe {
+            let board = ws_board_parse("ab c€\0".as_ptr() as *const c_char, error.as_mut_ptr(), 21);
+
+            assert!(board.is_null());
+            assert_eq!(
+                CStr::from_ptr(error.as_ptr()).to_str(),
+                Ok("invalid character '")
+            );
+        }
+    }
+
+    // The C spelling of a Rust parameter or return type used above.
+    fn c_type(rust: &str) -> String {
+        let (qualifier, inner) = match (rust.strip_prefix("*const "), rust.strip_prefix("*mut ")) {
+            (Some(inner), _) => ("const ", inner),
+            (_, Some(inner)) => ("", inner),
+            _ => ("", rust),
+        };
+        let name = match inner {
+            "c_char" => "char",
+            "u32" => "uint32_t",
+            "usize" => "size_t",
+            "Dict" => "ws_dict",
+            "Board" => "ws_board",
+            "Results" => "ws_results",
+            other => panic!("no C type for {}", other),
+        };
+        if inner == rust {
+            format!("{} ", name)
+        } else {
+            format!("{}{} *", qualifier, name)
+        }
+    }
+
+    // The C prototype of each `extern "C"` function in `source`.
+    fn prototypes(source: &str) -> Vec<String> {
+        let marker = "pub unsafe extern \"C\" fn ";
+        source
+            .split(marker)
+            .skip(1)
+            .map(|rest| {
+                let signature = rest[..rest.find('{').unwrap()].split_whitespace();
+                let signature = signature.collect::<Vec<_>>().join(" ");
+                let (name, rest) = signature.split_once('(').unwrap();
+                let (params, ret) = rest.rsplit_once(')').unwrap();
+                let params: Vec<String> = params
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|param| !param.is_empty())
+                    .map(|param| {
+                        let (name, rust) = param.split_once(": ").unwrap();
+                        format!("{}{}", c_type(rust), name)
+                    })
+                    .collect();
+                let ret = match ret.trim().strip_prefix("-> ") {
+                    Some(rust) => c_type(rust),
+                    None => "void ".to_string(),
+                };
+                format!("{}{}({});", ret, name, params.join(", "))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn header_matches_signatures() {
+        let header = include_str!("../include/wordsearch.h");
+        let source = include_str!("ffi.rs");
+        // split this file where the tests start, so the marker in
+        // `prototypes` isn't taken for a function
+        let source = &source[..source.find("// test ffi").unwrap()];
+
+        let mut declared: Vec<String> = header
+            .lines()
+            .filter(|line| line.starts_with("ws_") || line.contains(" ws_"))
+            .filter(|line| line.ends_with(");") && !line.starts_with(' '))
+            .map(str::to_string)
+            .collect();
+        let mut expected = prototypes(source);
+        declared.sort();
+        expected.sort();
+
+        assert_eq!(expected.len(), 9);
+        assert_eq!(declared, expected);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{fold_case, text, Board, Dict};
+
+// Fuzzy
+
+/// A word close to a query, and how many edits away it is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    pub distance: usize,
+}
+
+impl Dict {
+    /// The words within `max_distance` Levenshtein edits (insertions,
+    /// deletions or substitutions of one letter) of `query`, closest first
+    /// and then in alphabetical order.
+    ///
+    /// This fills in one row of the edit distance table per trie node, so a
+    /// prefix shared by many words is only worked out once, and gives up on
+    /// a branch once every entry of its row is over the limit.
+    pub fn near(&self, query: &str, max_distance: usize) -> Vec<Suggestion> {
+        let query: Vec<char> = text::compose_latin(query).chars().map(fold_case).collect();
+        let row: Vec<usize> = (0..=query.len()).collect();
+        let mut found = vec![];
+        near_step(
+            self,
+            &query,
+            &row,
+            max_distance,
+            &mut String::new(),
+            &mut found,
+        );
+        found.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        found
+    }
+}
+
+fn near_step(
+    dict: &Dict,
+    query: &[char],
+    row: &[usize],
+    max_distance: usize,
+    word: &mut String,
+    found: &mut Vec<Suggestion>,
+) {
+    for (&c, next) in dict.next.iter() {
+        let mut next_row = Vec::with_capacity(row.len());
+        next_row.push(row[0] + 1);
+        for i in 1..row.len() {
+            let substitute = row[i - 1] + if query[i - 1] == c { 0 } else { 1 };
+            next_row.push(substitute.min(row[i] + 1).min(next_row[i - 1] + 1));
+        }
+
+        word.push(c);
+        let distance = next_row[query.len()];
+        if next.end && distance <= max_distance {
+            found.push(Suggestion {
+                word: word.clone(),
+                distance,
+            });
+        }
+        if next_row.iter().any(|&d| d <= max_distance) {
+            near_step(next, query, &next_row, max_distance, word, found);
+        }
+        word.pop();
+    }
+}
+
+impl Board {
+    /// Like `Dict::near`, but only the words that can be traced on the board.
+    pub fn near(&self, dict: &Dict, query: &str, max_distance: usize) -> Vec<Suggestion> {
+        dict.near(query, max_distance)
+            .into_iter()
+            .filter(|suggestion| self.find_word(&suggestion.word).is_some())
+            .collect()
+    }
+}
+
+// test Fuzzy
+
+#[cfg(test)]
+mod test_fuzzy {
+    use super::*;
+
+    fn words(suggestions: &[Suggestion]) -> Vec<(&str, usize)> {
+        suggestions
+            .iter()
+            .map(|s| (s.word.as_str(), s.distance))
+            .collect()
+    }
+
+    #[test]
+    fn dict_near() {
+        let dict: Dict = [
+            "an", "ant", "bat", "cat", "coat", "tab", "tan", "nab", "tabs",
+        ]
+        .iter()
+        .copied()
+        .collect();
+
+        assert_eq!(words(&dict.near("cat", 0)), vec![("cat", 0)]);
+        assert_eq!(
+            words(&dict.near("cat", 1)),
+            vec![("cat", 0), ("bat", 1), ("coat", 1)]
+        );
+        assert_eq!(
+            words(&dict.near("Nat", 1)),
+            vec![("bat", 1), ("cat", 1), ("nab", 1)]
+        );
+        assert_eq!(words(&dict.near("tbs", 1)), vec![("tabs", 1)]);
+        assert!(dict.near("zzzz", 2).is_empty());
+    }
+
+    #[test]
+    fn board_near() {
+        let dict: Dict = [
+            "an", "ant", "bat", "cat", "coat", "tab", "tan", "nab", "tabs",
+        ]
+        .iter()
+        .copied()
+        .collect();
+        let board: Board = "ba tn".parse().unwrap();
+
+        assert_eq!(words(&board.near(&dict, "cat", 1)), vec![("bat", 1)]);
+        assert_eq!(
+            words(&board.near(&dict, "tabs", 2)),
+            vec![("tab", 1), ("nab", 2), ("tan", 2)]
+        );
+    }
+}
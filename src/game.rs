@@ -0,0 +1,284 @@
+use crate::score::ScoreTable;
+use crate::{fold_case, text, Board, Dict, Path};
+use std::collections::BTreeMap as Map;
+use std::fmt;
+
+// Rejection
+
+/// Why a guessed word doesn't count.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rejection {
+    TooShort {
+        min_length: usize,
+    },
+    NotOnBoard,
+    NotInDictionary,
+    AlreadyFound,
+    /// Also found by the named players, so no one scores it.
+    Shared(Vec<String>),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::TooShort { min_length } => {
+                write!(f, "too short, words need {} letters", min_length)
+            }
+            Rejection::NotOnBoard => write!(f, "not on the board"),
+            Rejection::NotInDictionary => write!(f, "not in the dictionary"),
+            Rejection::AlreadyFound => write!(f, "already found"),
+            Rejection::Shared(players) => write!(f, "also found by {}", players.join(", ")),
+        }
+    }
+}
+
+/// The form a guess is compared in: trimmed, with its accents composed and
+/// lowercased, as the board and `Dict` store letters.
+pub fn normalize(word: &str) -> String {
+    text::compose_latin(word.trim())
+        .chars()
+        .map(fold_case)
+        .collect()
+}
+
+/// Checks a guess against the rules: long enough to score, traceable on the
+/// board, and in the dictionary. Returns the word's path if it counts.
+pub fn judge(
+    board: &Board,
+    dict: &Dict,
+    scores: &ScoreTable,
+    word: &str,
+) -> Result<Path, Rejection> {
+    let min_length = scores.min_length();
+    if word.chars().count() < min_length {
+        return Err(Rejection::TooShort { min_length });
+    }
+    let path = board.find_word(word).ok_or(Rejection::NotOnBoard)?;
+    if !dict.contains(word) {
+        return Err(Rejection::NotInDictionary);
+    }
+    Ok(path)
+}
+
+// Round
+
+/// A multiplayer round on one board, scored as in Boggle: each player's valid
+/// words score by the table, except words more than one player found, which
+/// score for no one.
+pub struct Round<'a> {
+    board: &'a Board,
+    dict: &'a Dict,
+    scores: &'a ScoreTable,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tally {
+    pub player: String,
+    pub score: u32,
+    /// Each word as the player gave it, normalized, in their order, with its
+    /// points or why it was rejected.
+    pub words: Vec<(String, Result<u32, Rejection>)>,
+}
+
+impl<'a> Round<'a> {
+    pub fn new(board: &'a Board, dict: &'a Dict, scores: &'a ScoreTable) -> Round<'a> {
+        Round {
+            board,
+            dict,
+            scores,
+        }
+    }
+
+    /// Scores each player's list of words, returning a tally per player in
+    /// the order given.
+    pub fn score(&self, players: &[(String, Vec<String>)]) -> Vec<Tally> {
+        // judge every word, then count who found each valid one, by index so
+        // that players with the same name are still told apart
+        let mut judged: Vec<Vec<(String, Result<u32, Rejection>)>> = vec![];
+        let mut finders: Map<String, Vec<usize>> = Map::new();
+        for (index, (_, words)) in players.iter().enumerate() {
+            let mut results: Vec<(String, Result<u32, Rejection>)> = vec![];
+            for word in words {
+                let word = normalize(word);
+                // a repeat only counts as already found if it counted the
+                // first time; otherwise it fails the same way again
+                let result = match results.iter().find(|(w, _)| *w == word) {
+                    Some((_, Ok(_))) => Err(Rejection::AlreadyFound),
+                    Some((_, Err(rejection))) => Err(rejection.clone()),
+                    None => judge(self.board, self.dict, self.scores, &word)
+                        .map(|_| self.scores.score_word(&word)),
+                };
+                if result.is_ok() {
+                    finders.entry(word.clone()).or_default().push(index);
+                }
+                results.push((word, result));
+            }
+            judged.push(results);
+        }
+
+        players
+            .iter()
+            .zip(judged)
+            .enumerate()
+            .map(|(index, ((player, _), results))| {
+                let words: Vec<(String, Result<u32, Rejection>)> = results
+                    .into_iter()
+                    .map(|(word, result)| {
+                        let others: Vec<String> = finders
+                            .get(&word)
+                            .map(|found_by| {
+                                found_by
+                                    .iter()
+                                    .filter(|&&i| i != index)
+                                    .map(|&i| players[i].0.clone())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        match result {
+                            Ok(_) if !others.is_empty() => (word, Err(Rejection::Shared(others))),
+                            result => (word, result),
+                        }
+                    })
+                    .collect();
+                Tally {
+                    player: player.clone(),
+                    score: words.iter().filter_map(|(_, r)| r.as_ref().ok()).sum(),
+                    words,
+                }
+            })
+            .collect()
+    }
+}
+
+// test game
+
+#[cfg(test)]
+mod test_game {
+    use super::*;
+
+    #[test]
+    fn judge() {
+        let dict: Dict = ["an", "ant", "bat", "tab", "tabs"]
+            .iter()
+            .copied()
+            .collect();
+        let board: Board = "ba tn".parse().unwrap();
+        let scores = ScoreTable::boggle();
+
+        assert_eq!(
+            super::judge(&board, &dict, &scores, "bat"),
+            Ok(vec![0, 1, 2])
+        );
+        assert_eq!(
+            super::judge(&board, &dict, &scores, "TAB"),
+            Ok(vec![2, 1, 0])
+        );
+        assert_eq!(
+            super::judge(&board, &dict, &scores, "an"),
+            Err(Rejection::TooShort { min_length: 3 })
+        );
+        assert_eq!(
+            super::judge(&board, &dict, &scores, "tabs"),
+            Err(Rejection::NotOnBoard)
+        );
+        assert_eq!(
+            super::judge(&board, &dict, &scores, "nab"),
+            Err(Rejection::NotInDictionary)
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(super::normalize("  E\u{301}TÉ\n"), "été");
+    }
+
+    #[test]
+    fn round() {
+        let dict: Dict = ["an", "ant", "bat", "tab", "tan", "nab", "bant"]
+            .iter()
+            .copied()
+            .collect();
+        let board: Board = "ba tn".parse().unwrap();
+        let scores = ScoreTable::boggle();
+        let players = vec![
+            (
+                "ann".to_string(),
+                vec![
+                    "bat".to_string(),
+                    "Tab".to_string(),
+                    "an".to_string(),
+                    "tab".to_string(),
+                ],
+            ),
+            (
+                "bo".to_string(),
+                vec!["tab".to_string(), "ant".to_string(), "xyz".to_string()],
+            ),
+            ("cy".to_string(), vec!["tab".to_string(), "nat".to_string()]),
+        ];
+
+        let tallies = Round::new(&board, &dict, &scores).score(&players);
+
+        assert_eq!(
+            tallies[0],
+            Tally {
+                player: "ann".to_string(),
+                score: 1,
+                words: vec![
+                    ("bat".to_string(), Ok(1)),
+                    (
+                        "tab".to_string(),
+                        Err(Rejection::Shared(vec!["bo".to_string(), "cy".to_string()]))
+                    ),
+                    ("an".to_string(), Err(Rejection::TooShort { min_length: 3 })),
+                    ("tab".to_string(), Err(Rejection::AlreadyFound)),
+                ],
+            }
+        );
+        assert_eq!(tallies[1].score, 1);
+        assert_eq!(
+            tallies[1].words[2],
+            ("xyz".to_string(), Err(Rejection::NotOnBoard))
+        );
+        assert_eq!(tallies[2].score, 0);
+        assert_eq!(
+            tallies[2].words[1],
+            ("nat".to_string(), Err(Rejection::NotInDictionary))
+        );
+        assert_eq!(
+            Rejection::Shared(vec!["bo".to_string(), "cy".to_string()]).to_string(),
+            "also found by bo, cy"
+        );
+    }
+
+    #[test]
+    fn round_repeats_and_names() {
+        let dict: Dict = ["bat", "tab"].iter().copied().collect();
+        let board: Board = "ba tn".parse().unwrap();
+        let scores = ScoreTable::boggle();
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        let players = vec![
+            (
+                "ann".to_string(),
+                words(&["xyz", "xyz", "bat", "bat", "tab"]),
+            ),
+            ("ann".to_string(), words(&["tab"])),
+        ];
+
+        let tallies = Round::new(&board, &dict, &scores).score(&players);
+
+        // two players called ann are still two players, so tab is shared
+        let shared = Err(Rejection::Shared(vec!["ann".to_string()]));
+        assert_eq!(
+            tallies[0].words,
+            vec![
+                ("xyz".to_string(), Err(Rejection::NotOnBoard)),
+                ("xyz".to_string(), Err(Rejection::NotOnBoard)),
+                ("bat".to_string(), Ok(1)),
+                ("bat".to_string(), Err(Rejection::AlreadyFound)),
+                ("tab".to_string(), shared.clone()),
+            ]
+        );
+        assert_eq!(tallies[1].words, vec![("tab".to_string(), shared)]);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::dice::Dice;
+use crate::score::ScoreTable;
+use crate::{fold_case, text, Board, Cell, Dict};
+use std::collections::BTreeMap as Map;
+use std::collections::BTreeSet as Set;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Language
+
+/// A lexicon with the alphabet, dice and scoring used to play in it.
+#[derive(Debug)]
+pub struct Language {
+    pub name: String,
+    pub alphabet: Vec<char>,
+    pub dice: Option<Dice>,
+    pub scores: ScoreTable,
+    pub dict: Dict,
+}
+
+impl Language {
+    /// A language with no dice, Boggle scoring, and the alphabet of its words.
+    pub fn new<'a, T>(name: &str, words: T) -> Language
+    where
+        T: IntoIterator<Item = &'a str>,
+    {
+        let mut dict = Dict::new();
+        let mut alphabet = Set::new();
+        for word in words {
+            dict.add_word(word);
+            alphabet.extend(text::compose_latin(word).chars().map(fold_case));
+        }
+
+        Language {
+            name: name.to_string(),
+            alphabet: alphabet.into_iter().collect(),
+            dice: None,
+            scores: ScoreTable::boggle(),
+            dict,
+        }
+    }
+
+    /// Checks that every letter on `board` is in the alphabet, returning the
+    /// first that isn't.
+    pub fn check_board(&self, board: &Board) -> Result<(), ForeignLetter> {
+        let foreign = board
+            .cells()
+            .filter(|(_, c)| !self.alphabet.contains(c))
+            .find_map(|(pos, letter)| Some((board.cell(pos)?, letter)));
+        match foreign {
+            Some((cell, letter)) => Err(ForeignLetter {
+                language: self.name.clone(),
+                cell,
+                letter,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Loads a language pack from a directory holding:
+    ///
+    /// - `words.txt`, one word per line (required)
+    /// - `alphabet.txt`, the letters of the alphabet, which boards are checked
+    ///   against (default: the letters of the words)
+    /// - `dice.txt`, one die per line, written as its faces
+    /// - `scores.txt`, lines of `length points`; a length not listed scores
+    ///   the same as the next shorter one
+    ///
+    /// The pack is named after the directory. Accented letters must be
+    /// precomposed or in `text::compose_latin`'s table, which covers the
+    /// Latin alphabets listed there; a pack with any other combining mark,
+    /// which would never match a board cell, is rejected as `Unsupported`.
+    pub fn load(dir: &Path) -> Result<Language, LoadError> {
+        let name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let words = read_letters(&dir.join("words.txt"))?;
+        let mut language = Language::new(
+            &name,
+            words.lines().map(str::trim).filter(|w| !w.is_empty()),
+        );
+
+        if let Some(alphabet) = read_optional(&dir.join("alphabet.txt"))? {
+            let alphabet = check_letters(&dir.join("alphabet.txt"), alphabet)?;
+            let letters: Set<char> = text::compose_latin(&alphabet)
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(fold_case)
+                .collect();
+            language.alphabet = letters.into_iter().collect();
+        }
+        if let Some(dice) = read_optional(&dir.join("dice.txt"))? {
+            let dice = check_letters(&dir.join("dice.txt"), dice)?;
+            language.dice = Some(Dice::parse(&text::compose_latin(&dice).to_lowercase()));
+        }
+        let scores_path = dir.join("scores.txt");
+        if let Some(scores) = read_optional(&scores_path)? {
+            language.scores = parse_scores(&scores).map_err(|line| LoadError::Parse {
+                path: scores_path,
+                line,
+            })?;
+        }
+
+        Ok(language)
+    }
+}
+
+fn read(path: &Path) -> Result<String, LoadError> {
+    fs::read_to_string(path).map_err(|error| LoadError::Io(path.to_path_buf(), error))
+}
+
+fn read_letters(path: &Path) -> Result<String, LoadError> {
+    check_letters(path, read(path)?)
+}
+
+// Fails on the first line with a combining mark that `text::compose_latin`
+// can't compose into its letter.
+fn check_letters(path: &Path, s: String) -> Result<String, LoadError> {
+    for (i, line) in s.lines().enumerate() {
+        if let Some(mark) = text::compose_latin(line)
+            .chars()
+            .find(|&c| text::is_combining_mark(c))
+        {
+            return Err(LoadError::Unsupported {
+                path: path.to_path_buf(),
+                line: i + 1,
+                mark,
+            });
+        }
+    }
+    Ok(s)
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, LoadError> {
+    if path.exists() {
+        read(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+// Returns the number of the first bad line, counting from 1.
+fn parse_scores(s: &str) -> Result<ScoreTable, usize> {
+    let mut pairs: Map<usize, u32> = Map::new();
+    for (i, line) in s.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [] => {}
+            [length, points] => match (length.parse(), points.parse()) {
+                (Ok(length), Ok(points)) => {
+                    pairs.insert(length, points);
+                }
+                _ => return Err(i + 1),
+            },
+            _ => return Err(i + 1),
+        }
+    }
+
+    let longest = pairs.keys().last().copied().unwrap_or(0);
+    let mut points = vec![0; longest + 1];
+    for length in 0..=longest {
+        points[length] = match pairs.get(&length) {
+            Some(&p) => p,
+            None if length > 0 => points[length - 1],
+            None => 0,
+        };
+    }
+    Ok(ScoreTable::new(points))
+}
+
+/// A letter on a board that isn't in the language's alphabet.
+#[derive(Debug, PartialEq)]
+pub struct ForeignLetter {
+    pub language: String,
+    pub cell: Cell,
+    pub letter: char,
+}
+
+impl fmt::Display for ForeignLetter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} at {} is not in the {} alphabet",
+            self.letter, self.cell, self.language
+        )
+    }
+}
+
+impl Error for ForeignLetter {}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Unknown(String),
+    Io(PathBuf, io::Error),
+    Parse {
+        path: PathBuf,
+        line: usize,
+    },
+    /// A letter with an accent that only NFC could compose.
+    Unsupported {
+        path: PathBuf,
+        line: usize,
+        mark: char,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Unknown(name) => write!(f, "unknown language: {}", name),
+            LoadError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            LoadError::Parse { path, line } => {
+                write!(f, "{}: invalid line {}", path.display(), line)
+            }
+            LoadError::Unsupported { path, line, mark } => write!(
+                f,
+                "{}: line {} has the accent U+{:04X}, which is only supported on \
+                 the Latin letters of the languages in text::compose_latin's table",
+                path.display(),
+                line,
+                *mark as u32
+            ),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+// Registry
+
+enum Source {
+    Builtin(fn() -> Language),
+    Dir(PathBuf),
+}
+
+/// Named languages, either built in or found in a directory of packs. Packs
+/// are only read when loaded by name.
+#[derive(Default)]
+pub struct Registry {
+    sources: Map<String, Source>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    pub fn add_builtin(&mut self, name: &str, load: fn() -> Language) -> &Registry {
+        self.sources.insert(name.to_string(), Source::Builtin(load));
+        self
+    }
+
+    /// Registers each subdirectory of `dir` that has a `words.txt` as a pack
+    /// named after the subdirectory. Packs replace built-in languages of the
+    /// same name.
+    pub fn scan(&mut self, dir: &Path) -> io::Result<&Registry> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.join("words.txt").is_file() {
+                if let Some(name) = path.file_name() {
+                    let name = name.to_string_lossy().into_owned();
+                    self.sources.insert(name, Source::Dir(path));
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.sources.keys().map(String::as_str).collect()
+    }
+
+    pub fn load(&self, name: &str) -> Result<Language, LoadError> {
+        match self.sources.get(name) {
+            Some(Source::Builtin(load)) => Ok(load()),
+            Some(Source::Dir(path)) => Language::load(path),
+            None => Err(LoadError::Unknown(name.to_string())),
+        }
+    }
+}
+
+// test Language
+
+#[cfg(test)]
+mod test_language {
+    use super::*;
+    use std::env;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("wordsearch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn new() {
+        let language = Language::new("xx", vec!["Bat", "tab", "été"]);
+
+        assert_eq!(language.alphabet, vec!['a', 'b', 't', 'é']);
+        assert!(language.dict.contains("BAT"));
+        assert_eq!(language.scores, ScoreTable::boggle());
+        assert_eq!(language.dice, None);
+    }
+
+    #[test]
+    fn check_board() {
+        let language = Language::new("xx", vec!["Bat", "tab", "été"]);
+
+        assert_eq!(language.check_board(&"bat téa".parse().unwrap()), Ok(()));
+        assert_eq!(
+            language.check_board(&"bat tax".parse().unwrap()),
+            Err(ForeignLetter {
+                language: "xx".to_string(),
+                cell: Cell { row: 1, col: 2 },
+                letter: 'x',
+            })
+        );
+        assert_eq!(
+            language
+                .check_board(&"bx ta".parse().unwrap())
+                .unwrap_err()
+                .to_string(),
+            "'x' at 0:1 is not in the xx alphabet"
+        );
+    }
+
+    #[test]
+    fn parse_scores() {
+        assert_eq!(
+            super::parse_scores("3 1\n5 2\n\n6 4\n"),
+            Ok(ScoreTable::new(vec![0, 0, 0, 1, 1, 2, 4]))
+        );
+        assert_eq!(super::parse_scores("3 1\nx 2\n"), Err(2));
+    }
+
+    #[test]
+    fn registry() {
+        let dir = temp_dir("registry");
+        let fr = dir.join("fr");
+        fs::create_dir_all(&fr).unwrap();
+        fs::write(fr.join("words.txt"), "été\nmaïs\n").unwrap();
+        fs::write(
+            fr.join("alphabet.txt"),
+            "abcdefghijklmnopqrstuvwxyz éèêëàâîïôûùüç",
+        )
+        .unwrap();
+        fs::write(fr.join("dice.txt"), "ÉTEAIS\nMAÏSOU\n").unwrap();
+        fs::write(fr.join("scores.txt"), "3 1\n4 2\n").unwrap();
+        fs::create_dir_all(dir.join("empty")).unwrap();
+
+        let mut registry = Registry::new();
+        registry.add_builtin("en-us", || Language::new("en-us", vec!["cat"]));
+        registry.scan(&dir).unwrap();
+
+        assert_eq!(registry.names(), vec!["en-us", "fr"]);
+
+        let fr = registry.load("fr").unwrap();
+        assert_eq!(fr.name, "fr");
+        assert_eq!(fr.alphabet.len(), 39);
+        assert!(fr.dict.contains("MAÏS"));
+        assert_eq!(fr.dice.unwrap().0[0], vec!['é', 't', 'e', 'a', 'i', 's']);
+        assert_eq!(fr.scores, ScoreTable::new(vec![0, 0, 0, 1, 2]));
+
+        assert!(registry.load("en-us").unwrap().dict.contains("cat"));
+        assert!(matches!(registry.load("de"), Err(LoadError::Unknown(_))));
+
+        // Vietnamese ệ is e, a dot below and a circumflex, which the table
+        // doesn't compose
+        let vi = dir.join("vi");
+        fs::create_dir_all(&vi).unwrap();
+        fs::write(vi.join("words.txt"), "an\nbe\u{323}\u{302}n\n").unwrap();
+        match Language::load(&vi) {
+            Err(LoadError::Unsupported { line, mark, .. }) => {
+                assert_eq!((line, mark), (2, '\u{323}'))
+            }
+            other => panic!("expected Unsupported, got {:?}", other),
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,32 +1,88 @@
 use std::collections::BTreeMap as Map;
 use std::collections::BTreeSet as Set;
+use std::error::Error;
+use std::fmt;
 use std::iter::FromIterator;
 use std::str::FromStr;
 
+pub mod analysis;
+pub mod dice;
+pub mod ffi;
+pub mod fuzzy;
+pub mod game;
+pub mod lang;
+pub mod optimize;
+pub mod parallel;
+pub mod pattern;
+pub mod rack;
+pub mod render;
+pub mod rng;
+pub mod score;
+pub mod solved;
+pub mod svg;
+pub mod symmetry;
+pub mod text;
+
 // Dict
 
 #[derive(Debug, PartialEq)]
 pub struct Dict {
     end: bool,
+    // the length of the longest word continuing from here
+    height: usize,
     next: Map<char, Dict>,
 }
 
+impl Default for Dict {
+    fn default() -> Self {
+        Dict::new()
+    }
+}
+
 impl Dict {
     pub fn new() -> Dict {
         Dict {
             end: false,
+            height: 0,
             next: Map::new(),
         }
     }
 
+    /// Adds `word`, with its accents composed by `text::compose_latin` and
+    /// lowercased, so that lookups ignore case. Accented letters outside that
+    /// function's table stay as a letter and a combining mark.
     pub fn add_word(&mut self, word: &str) -> &Dict {
+        let chars: Vec<char> = text::compose_latin(word).chars().map(fold_case).collect();
         let mut dict: &mut Dict = self;
-        for c in word.chars() {
-            dict = dict.next.entry(c).or_insert_with(|| Dict::new());
+        for (i, &c) in chars.iter().enumerate() {
+            dict.height = dict.height.max(chars.len() - i);
+            dict = dict.next.entry(c).or_default();
         }
         dict.end = true;
         self
     }
+
+    // The node reached by spelling the tile `c`: with `qu_tiles`, a `q` tile
+    // spells "qu".
+    fn next_tile(&self, c: char, qu_tiles: bool) -> Option<&Dict> {
+        let next = self.next.get(&c)?;
+        if qu_tiles && c == QU_TILE {
+            next.next.get(&'u')
+        } else {
+            Some(next)
+        }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        let mut dict: &Dict = self;
+        for c in text::compose_latin(word).chars().map(fold_case) {
+            match dict.next.get(&c) {
+                Some(next) => dict = next,
+                None => return false,
+            }
+        }
+        dict.end
+    }
 }
 
 impl<'a> FromIterator<&'a str> for Dict {
@@ -42,14 +98,36 @@ impl<'a> FromIterator<&'a str> for Dict {
     }
 }
 
+impl FromIterator<String> for Dict {
+    fn from_iter<T>(words: T) -> Self
+    where
+        T: IntoIterator<Item = String>,
+    {
+        let mut dict = Dict::new();
+        for word in words {
+            dict.add_word(&word);
+        }
+        dict
+    }
+}
+
+/// Lowercases `c`, unless its lowercase form is more than one character.
+pub fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
 // Graph
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 struct Graph(Map<u32, Set<u32>>);
 
 impl Graph {
     fn add_directed_edge(&mut self, x: u32, y: u32) -> &Graph {
-        self.0.entry(x).or_insert_with(|| Set::new()).insert(y);
+        self.0.entry(x).or_default().insert(y);
         self
     }
 
@@ -59,6 +137,17 @@ impl Graph {
         self
     }
 
+    fn remove_node(&mut self, x: u32) -> &Graph {
+        if let Some(edges) = self.0.remove(&x) {
+            for y in edges {
+                if let Some(back) = self.0.get_mut(&y) {
+                    back.remove(&x);
+                }
+            }
+        }
+        self
+    }
+
     fn grid(width: u32, height: u32) -> Graph {
         let mut graph = Graph(Map::new());
 
@@ -87,90 +176,528 @@ impl Graph {
 
 // Board
 
-#[derive(Debug, PartialEq)]
+/// The cell letter for the "Qu" tile, on boards parsed with
+/// `ParseOptions::qu_tiles`. Like a Boggle die's "Qu" face, it spells the two
+/// letters "qu" wherever a word is traced, so "quit" takes three cells and a
+/// word like "qat" can't be made.
+pub const QU_TILE: char = 'q';
+
+// The rest of `word` after spelling the tile `c`, if it starts with it.
+fn spell_tile(word: &[char], c: char, qu_tiles: bool) -> Option<&[char]> {
+    let qu = qu_tiles && c == QU_TILE;
+    match word {
+        [first, 'u', rest @ ..] if *first == c && qu => Some(rest),
+        [first, rest @ ..] if *first == c && !qu => Some(rest),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Board {
+    width: u32,
+    height: u32,
     grid: Graph,
     chars: Map<u32, char>,
+    // whether `QU_TILE` cells spell "qu"
+    qu_tiles: bool,
 }
 
 pub type Path = Vec<u32>;
 
+/// A position on a board by row and column, counting from 0 at the top left.
+/// Shown as `row:col`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cell {
+    pub row: u32,
+    pub col: u32,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.row, self.col)
+    }
+}
+
+impl FromStr for Cell {
+    type Err = CellParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || CellParseError(s.to_string());
+        let (row, col) = s.split_once(':').ok_or_else(error)?;
+        Ok(Cell {
+            row: row.trim().parse().map_err(|_| error())?,
+            col: col.trim().parse().map_err(|_| error())?,
+        })
+    }
+}
+
+/// A cell that isn't written as `row:col`.
+#[derive(Debug, PartialEq)]
+pub struct CellParseError(pub String);
+
+impl fmt::Display for CellParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid cell {:?}, expected row:col", self.0)
+    }
+}
+
+impl Error for CellParseError {}
+
+/// A `Path` as cells rather than positions, for showing to people. Shown as a
+/// space-separated list of cells.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CellPath(pub Vec<Cell>);
+
+impl fmt::Display for CellPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, cell) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", cell)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PathError {
+    Empty,
+    OutOfRange { step: usize, pos: u32 },
+    NotAdjacent { step: usize, from: u32, to: u32 },
+    Repeated { step: usize, pos: u32 },
+    NotAWord(String),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathError::Empty => write!(f, "path is empty"),
+            PathError::OutOfRange { step, pos } => {
+                write!(f, "step {}: cell {} is not on the board", step, pos)
+            }
+            PathError::NotAdjacent { step, from, to } => {
+                write!(
+                    f,
+                    "step {}: cell {} is not adjacent to cell {}",
+                    step, to, from
+                )
+            }
+            PathError::Repeated { step, pos } => {
+                write!(f, "step {}: cell {} is already used", step, pos)
+            }
+            PathError::NotAWord(word) => write!(f, "\"{}\" is not in the dictionary", word),
+        }
+    }
+}
+
+impl Error for PathError {}
+
 impl Board {
     pub fn search(&self, dict: &Dict) -> Vec<Path> {
-        let path = vec![];
-        self.grid
-            .0
-            .keys()
-            .flat_map(|&pos| self.search_step(dict, &path, pos))
-            .collect()
-    }
-
-    fn search_step(&self, dict: &Dict, path: &Path, pos: u32) -> Vec<Path> {
-        match dict.next.get(self.chars.get(&pos).unwrap()) {
-            Some(dict_here) => {
-                let mut path_here = path.clone();
-                path_here.push(pos);
-
-                // let mut moves = self.grid.0.get(&pos).unwrap().clone();
-                // for visited in path_here.iter() {
-                //     moves.remove(visited);
-                // }
-                // let mut results: Vec<Path> = moves
-                //     .iter()
-                //     .flat_map(|&next| self.search_step(&dict_here, &path_here, next))
-                //     .collect();
-
-                let mut results: Vec<Path> = (self.grid.0.get(&pos).unwrap())
-                    .iter()
-                    .filter(|next| !path_here.contains(next))
-                    .flat_map(|&next| self.search_step(&dict_here, &path_here, next))
-                    .collect();
-
-                if dict_here.end {
-                    results.push(path_here);
+        let mut path = vec![];
+        let mut results = vec![];
+        for &pos in self.grid.0.keys() {
+            self.search_step(dict, &mut path, pos, &mut results);
+        }
+        results
+    }
+
+    /// Searches like `search`, with the starting cells split across up to
+    /// `threads` threads. The paths come back in the same order as `search`.
+    pub fn search_parallel(&self, dict: &Dict, threads: usize) -> Vec<Path> {
+        let starts: Vec<u32> = self.grid.0.keys().copied().collect();
+        parallel::map(&starts, threads, |&pos| {
+            let mut results = vec![];
+            self.search_step(dict, &mut vec![], pos, &mut results);
+            results
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    // `path` is a shared buffer, pushed on the way down and popped on the way
+    // back up, so only the paths that spell words are ever allocated.
+    fn search_step(&self, dict: &Dict, path: &mut Path, pos: u32, results: &mut Vec<Path>) {
+        if let Some(dict_here) = self
+            .chars
+            .get(&pos)
+            .and_then(|&c| dict.next_tile(c, self.qu_tiles))
+        {
+            path.push(pos);
+
+            for next in self.grid.0[&pos].iter() {
+                if !path.contains(next) {
+                    self.search_step(dict_here, path, *next, results);
                 }
-                results
             }
-            None => vec![],
+
+            if dict_here.end {
+                results.push(path.clone());
+            }
+            path.pop();
         }
     }
 
-    pub fn path_to_word(&self, path: &Path) -> String {
+    /// Finds a path that spells `word`, following the same adjacency and
+    /// no-reuse rules as `search`, without needing a `Dict`.
+    pub fn find_word(&self, word: &str) -> Option<Path> {
+        let word: Vec<char> = text::compose_latin(word).chars().map(fold_case).collect();
+        let mut path = vec![];
+        let mut results = vec![];
+        for &pos in self.grid.0.keys() {
+            if self.find_step(&word, &mut path, pos, &mut results, true) {
+                break;
+            }
+        }
+        results.pop()
+    }
+
+    /// Finds every path that spells `word`.
+    pub fn find_word_paths(&self, word: &str) -> Vec<Path> {
+        let word: Vec<char> = text::compose_latin(word).chars().map(fold_case).collect();
+        let mut path = vec![];
+        let mut results = vec![];
+        for &pos in self.grid.0.keys() {
+            self.find_step(&word, &mut path, pos, &mut results, false);
+        }
+        results
+    }
+
+    // Returns true once a path is found, if `first` is set, to stop the search.
+    fn find_step(
+        &self,
+        word: &[char],
+        path: &mut Path,
+        pos: u32,
+        results: &mut Vec<Path>,
+        first: bool,
+    ) -> bool {
+        match self
+            .chars
+            .get(&pos)
+            .and_then(|&c| spell_tile(word, c, self.qu_tiles))
+        {
+            Some(rest) => {
+                path.push(pos);
+
+                let mut done = false;
+                if rest.is_empty() {
+                    results.push(path.clone());
+                    done = first;
+                } else {
+                    for next in self.grid.0[&pos].iter() {
+                        if !path.contains(next) && self.find_step(rest, path, *next, results, first)
+                        {
+                            done = true;
+                            break;
+                        }
+                    }
+                }
+
+                path.pop();
+                done
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks that `path` could have been traced on this board: every cell
+    /// exists, each step moves to an adjacent cell, and no cell repeats. Steps
+    /// are numbered from 0 in errors.
+    pub fn validate_path(&self, path: &Path) -> Result<(), PathError> {
+        if path.is_empty() {
+            return Err(PathError::Empty);
+        }
+        for (step, &pos) in path.iter().enumerate() {
+            if !self.chars.contains_key(&pos) {
+                return Err(PathError::OutOfRange { step, pos });
+            }
+            if path[..step].contains(&pos) {
+                return Err(PathError::Repeated { step, pos });
+            }
+            if step > 0 {
+                let from = path[step - 1];
+                let adjacent = self
+                    .grid
+                    .0
+                    .get(&from)
+                    .is_some_and(|next| next.contains(&pos));
+                if !adjacent {
+                    return Err(PathError::NotAdjacent {
+                        step,
+                        from,
+                        to: pos,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates `path`, then checks that the word it spells is in `dict`,
+    /// returning the word.
+    pub fn validate_word(&self, path: &Path, dict: &Dict) -> Result<String, PathError> {
+        self.validate_path(path)?;
+        let word = self.path_to_word(path);
+        if dict.contains(&word) {
+            Ok(word)
+        } else {
+            Err(PathError::NotAWord(word))
+        }
+    }
+
+    pub fn words(&self, dict: &Dict) -> Vec<String> {
+        let words: Set<String> = self
+            .search(dict)
+            .iter()
+            .map(|path| self.path_to_word(path))
+            .collect();
+        words.into_iter().collect()
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Whether `QU_TILE` cells are "Qu" tiles, from `ParseOptions::qu_tiles`.
+    pub fn qu_tiles(&self) -> bool {
+        self.qu_tiles
+    }
+
+    pub fn char_at(&self, pos: u32) -> Option<char> {
+        self.chars.get(&pos).copied()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (u32, char)> + '_ {
+        self.chars.iter().map(|(&pos, &c)| (pos, c))
+    }
+
+    pub fn set_char(&mut self, pos: u32, c: char) -> Option<char> {
+        match self.chars.get_mut(&pos) {
+            Some(here) => Some(std::mem::replace(here, c)),
+            None => None,
+        }
+    }
+
+    /// The row and column of `pos`, or `None` if it is off the board.
+    pub fn cell(&self, pos: u32) -> Option<Cell> {
+        if pos < self.width * self.height {
+            Some(Cell {
+                row: pos / self.width,
+                col: pos % self.width,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The position of `cell`, or `None` if it is off the board.
+    pub fn pos(&self, cell: Cell) -> Option<u32> {
+        if cell.row < self.height && cell.col < self.width {
+            Some(cell.row * self.width + cell.col)
+        } else {
+            None
+        }
+    }
+
+    pub fn cell_path(&self, path: &Path) -> Option<CellPath> {
         path.iter()
-            .map(|pos| self.chars.get(pos).unwrap_or(&'?'))
-            .collect()
+            .map(|&pos| self.cell(pos))
+            .collect::<Option<_>>()
+            .map(CellPath)
+    }
+
+    pub fn path_from_cells(&self, cells: &CellPath) -> Option<Path> {
+        cells.0.iter().map(|&cell| self.pos(cell)).collect()
+    }
+
+    pub fn path_to_word(&self, path: &Path) -> String {
+        let mut word = String::new();
+        for pos in path {
+            match self.chars.get(pos) {
+                Some(&QU_TILE) if self.qu_tiles => word.push_str("qu"),
+                Some(&c) => word.push(c),
+                None => word.push('?'),
+            }
+        }
+        word
     }
 }
 
-impl FromStr for Board {
-    type Err = &'static str;
+/// What to do with a character on the board that is not a letter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonLetters {
+    Keep,
+    Reject,
+    /// Make the cell a hole that no path can use.
+    Void,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseOptions {
+    pub fold_case: bool,
+    /// Replace accented letters with their base letters, to play against a
+    /// dictionary built with `text::strip_accents`.
+    pub strip_accents: bool,
+    pub non_letters: NonLetters,
+    /// Read `QU_TILE` cells as the "Qu" tile, as on boards rolled from dice.
+    pub qu_tiles: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            fold_case: true,
+            strip_accents: false,
+            non_letters: NonLetters::Reject,
+            qu_tiles: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// The options for a board rolled from `Dice`: the defaults, with `q`
+    /// faces read as the "Qu" tile.
+    pub fn rolled() -> Self {
+        ParseOptions {
+            qu_tiles: true,
+            ..ParseOptions::default()
+        }
+    }
+}
+
+/// Why a board string could not be parsed. Rows and columns count from 0.
+#[derive(Debug, PartialEq)]
+pub enum BoardParseError {
+    TooFewRows {
+        found: usize,
+    },
+    TooFewColumns {
+        found: usize,
+    },
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidChar {
+        row: usize,
+        col: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for BoardParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardParseError::TooFewRows { found } => {
+                write!(f, "must have at least two rows, found {}", found)
+            }
+            BoardParseError::TooFewColumns { found } => {
+                write!(f, "must have at least two columns, found {}", found)
+            }
+            BoardParseError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "all rows must be the same width: row {} has width {}, expected {}",
+                row, found, expected
+            ),
+            BoardParseError::InvalidChar { row, col, found } => write!(
+                f,
+                "invalid character {:?} at row {}, column {}",
+                found, row, col
+            ),
+        }
+    }
+}
+
+impl Error for BoardParseError {}
+
+impl Board {
+    /// Parses rows separated by whitespace, applying `options` to each cell.
+    /// `str::parse` uses the default options: lowercase every letter and
+    /// reject anything else.
+    ///
+    /// Accents are composed first with `text::compose_latin`, so that a letter
+    /// followed by a combining accent is one cell. That only covers the Latin
+    /// letters in its table: any other combining mark is left as its own
+    /// character, which is not a letter and so is rejected as `InvalidChar`
+    /// (or handled as `non_letters` says).
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Board, BoardParseError> {
+        let s = text::compose_latin(s);
         let rows: Vec<&str> = s.split_whitespace().collect();
         let height = rows.len();
 
         if height < 2 {
-            return Err("must have at least two rows");
+            return Err(BoardParseError::TooFewRows { found: height });
         }
 
-        let width = rows[0].len();
+        let width = rows[0].chars().count();
 
-        if !rows.iter().all(|row| row.len() == width) {
-            return Err("all rows must be the same width");
+        if let Some((row, found)) = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .enumerate()
+            .find(|&(_, found)| found != width)
+        {
+            return Err(BoardParseError::RaggedRow {
+                row,
+                expected: width,
+                found,
+            });
         }
         if width < 2 {
-            return Err("must have at least two columns");
+            return Err(BoardParseError::TooFewColumns { found: width });
         }
 
-        let grid = Graph::grid(width as u32, height as u32);
-        let chars: Map<u32, char> = rows
-            .iter()
-            .flat_map(|row| row.chars())
-            .enumerate()
-            .map(|(i, c)| (i as u32, c))
-            .collect();
+        let mut grid = Graph::grid(width as u32, height as u32);
+        let mut chars: Map<u32, char> = Map::new();
+
+        for (row, line) in rows.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let pos = (row * width + col) as u32;
+                let letter = c.is_alphabetic();
+
+                if c.is_control() || (!letter && options.non_letters == NonLetters::Reject) {
+                    return Err(BoardParseError::InvalidChar { row, col, found: c });
+                }
+                if !letter && options.non_letters == NonLetters::Void {
+                    grid.remove_node(pos);
+                    continue;
+                }
+                let c = if options.strip_accents {
+                    text::strip_accent(c)
+                } else {
+                    c
+                };
+                chars.insert(pos, if options.fold_case { fold_case(c) } else { c });
+            }
+        }
 
-        Ok(Board { grid, chars })
+        Ok(Board {
+            width: width as u32,
+            height: height as u32,
+            grid,
+            chars,
+            qu_tiles: options.qu_tiles,
+        })
+    }
+}
+
+impl FromStr for Board {
+    type Err = BoardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Board::parse_with(s, &ParseOptions::default())
     }
 }
 
@@ -185,6 +712,8 @@ mod test_board {
         let board = "abc def ghi".parse::<Board>();
 
         let expected = Board {
+            width: 3,
+            height: 3,
             grid: Graph::grid(3, 3),
             chars: vec![
                 (0, 'a'),
@@ -199,6 +728,7 @@ mod test_board {
             ]
             .into_iter()
             .collect(),
+            qu_tiles: false,
         };
 
         assert_eq!(board, Ok(expected));
@@ -211,20 +741,155 @@ mod test_board {
         assert!("abc de fghi".parse::<Board>().is_err());
     }
 
+    #[test]
+    fn from_str_err_details() {
+        assert_eq!(
+            "abc".parse::<Board>(),
+            Err(BoardParseError::TooFewRows { found: 1 })
+        );
+        assert_eq!(
+            "a b c".parse::<Board>(),
+            Err(BoardParseError::TooFewColumns { found: 1 })
+        );
+        assert_eq!(
+            "abc def gh".parse::<Board>(),
+            Err(BoardParseError::RaggedRow {
+                row: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "abc d\u{7}f".parse::<Board>(),
+            Err(BoardParseError::InvalidChar {
+                row: 1,
+                col: 1,
+                found: '\u{7}'
+            })
+        );
+        assert_eq!(
+            "abc def gh".parse::<Board>().unwrap_err().to_string(),
+            "all rows must be the same width: row 2 has width 2, expected 3"
+        );
+    }
+
+    #[test]
+    fn parse_with() {
+        let keep = ParseOptions {
+            fold_case: false,
+            non_letters: NonLetters::Keep,
+            ..ParseOptions::default()
+        };
+        let void = ParseOptions {
+            non_letters: NonLetters::Void,
+            ..ParseOptions::default()
+        };
+
+        let board = "Ab c1".parse::<Board>();
+        let kept = Board::parse_with("Ab c1", &keep).unwrap();
+        let voided = Board::parse_with("Ab c1", &void).unwrap();
+
+        assert_eq!(
+            board,
+            Err(BoardParseError::InvalidChar {
+                row: 1,
+                col: 1,
+                found: '1'
+            })
+        );
+        assert_eq!(
+            kept.cells().collect::<Vec<_>>(),
+            vec![(0, 'A'), (1, 'b'), (2, 'c'), (3, '1')]
+        );
+        assert_eq!(
+            voided.cells().collect::<Vec<_>>(),
+            vec![(0, 'a'), (1, 'b'), (2, 'c')]
+        );
+        assert_eq!(voided.grid.0.get(&3), None);
+        assert_eq!(voided.grid.0[&0], vec![1, 2].into_iter().collect());
+    }
+
+    #[test]
+    fn search_ignores_case() {
+        let dict: Dict = ["Tab", "BAT"].iter().copied().collect();
+        let board: Board = "BAt NXy".parse().unwrap();
+
+        let mut words = board.words(&dict);
+        words.sort();
+
+        assert_eq!(words, vec!["bat", "tab"]);
+        assert_eq!(board.find_word("TAB"), Some(vec![2, 1, 0]));
+        assert!(dict.contains("bAt"));
+    }
+
+    #[test]
+    fn parse_unicode() {
+        let board: Board = "ÉTÉ ñu\u{301}n Żół".parse().unwrap();
+        let stripped = Board::parse_with(
+            "ÉTÉ ñu\u{301}n Żół",
+            &ParseOptions {
+                strip_accents: true,
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!((board.width(), board.height()), (3, 3));
+        assert_eq!(board.path_to_word(&vec![0, 1, 2, 3, 4]), "étéñú");
+        assert_eq!(board.path_to_word(&vec![6, 7, 8]), "żół");
+        assert_eq!(stripped.path_to_word(&vec![0, 1, 2, 6, 7, 8]), "etezol");
+        assert_eq!(
+            "ab e\u{301}\u{301}".parse::<Board>(),
+            Err(BoardParseError::InvalidChar {
+                row: 1,
+                col: 1,
+                found: '\u{301}'
+            })
+        );
+    }
+
+    #[test]
+    fn search_unicode() {
+        let dict: Dict = ["e\u{301}te\u{301}", "Żółw"].iter().copied().collect();
+        let board: Board = "étz éwż lół".parse().unwrap();
+
+        assert!(dict.contains("ÉTÉ"));
+        assert_eq!(board.words(&dict), vec!["été", "żółw"]);
+        assert_eq!(board.find_word("zolw"), None);
+
+        let plain: Dict = ["e\u{301}te\u{301}", "Żółw"]
+            .iter()
+            .map(|word| text::strip_accents(word))
+            .collect();
+        let stripped = Board::parse_with(
+            "étz éwż lół",
+            &ParseOptions {
+                strip_accents: true,
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(stripped.words(&plain), vec!["ete", "zolw"]);
+    }
+
     #[test]
     fn search() {
         let dict: Dict = [
             "an", "and", "ant", "anti", "bad", "banana", "bat", "bot", "boy",
         ]
         .iter()
-        .map(|s| *s)
+        .copied()
         .collect();
 
         let board = Board {
+            width: 2,
+            height: 2,
             grid: Graph::grid(2, 2),
             chars: vec![(0, 'b'), (1, 'a'), (2, 't'), (3, 'n')]
                 .into_iter()
                 .collect(),
+            qu_tiles: false,
         };
 
         let mut paths = board.search(&dict);
@@ -235,19 +900,166 @@ mod test_board {
         assert_eq!(paths, expected);
     }
 
+    #[test]
+    fn find_word() {
+        let board: Board = "ban tan".parse().unwrap();
+
+        assert_eq!(board.find_word("bat"), Some(vec![0, 1, 3]));
+        assert_eq!(board.find_word("nab"), Some(vec![2, 1, 0]));
+        assert_eq!(board.find_word("banana"), None);
+        assert_eq!(board.find_word("tt"), None);
+        assert_eq!(board.find_word(""), None);
+    }
+
+    #[test]
+    fn find_word_paths() {
+        let board: Board = "ban tan".parse().unwrap();
+
+        let mut paths = board.find_word_paths("an");
+        paths.sort();
+
+        assert_eq!(paths, vec![vec![1, 2], vec![1, 5], vec![4, 2], vec![4, 5]]);
+        assert_eq!(
+            board.find_word_paths("nab"),
+            vec![vec![2, 1, 0], vec![2, 4, 0], vec![5, 1, 0], vec![5, 4, 0]]
+        );
+    }
+
+    #[test]
+    fn validate_path() {
+        let board: Board = "ban tan".parse().unwrap();
+
+        assert_eq!(board.validate_path(&vec![0, 1, 3]), Ok(()));
+        assert_eq!(board.validate_path(&vec![]), Err(PathError::Empty));
+        assert_eq!(
+            board.validate_path(&vec![0, 1, 6]),
+            Err(PathError::OutOfRange { step: 2, pos: 6 })
+        );
+        assert_eq!(
+            board.validate_path(&vec![0, 2]),
+            Err(PathError::NotAdjacent {
+                step: 1,
+                from: 0,
+                to: 2
+            })
+        );
+        assert_eq!(
+            board.validate_path(&vec![0, 1, 0]),
+            Err(PathError::Repeated { step: 2, pos: 0 })
+        );
+    }
+
+    #[test]
+    fn validate_word() {
+        let dict: Dict = ["an", "bat", "tab"].iter().copied().collect();
+        let board: Board = "ban tan".parse().unwrap();
+
+        assert_eq!(
+            board.validate_word(&vec![0, 1, 3], &dict),
+            Ok("bat".to_string())
+        );
+        assert_eq!(
+            board.validate_word(&vec![0, 1, 2], &dict),
+            Err(PathError::NotAWord("ban".to_string()))
+        );
+        assert_eq!(
+            board.validate_word(&vec![0, 2], &dict),
+            Err(PathError::NotAdjacent {
+                step: 1,
+                from: 0,
+                to: 2
+            })
+        );
+    }
+
+    #[test]
+    fn search_parallel() {
+        let dict: Dict = [
+            "an", "and", "ant", "anti", "bad", "bat", "tab", "tan", "nab",
+        ]
+        .iter()
+        .copied()
+        .collect();
+        let board: Board = "bant dtab nabt".parse().unwrap();
+
+        let expected = board.search(&dict);
+
+        assert!(!expected.is_empty());
+        assert_eq!(board.search_parallel(&dict, 1), expected);
+        assert_eq!(board.search_parallel(&dict, 3), expected);
+        assert_eq!(board.search_parallel(&dict, 64), expected);
+    }
+
     #[test]
     fn path_to_word() {
         let board = Board {
+            width: 2,
+            height: 2,
             grid: Graph::grid(2, 2),
             chars: vec![(0, 'e'), (1, 'r'), (2, 'd'), (3, 'f')]
                 .into_iter()
                 .collect(),
+            qu_tiles: false,
         };
 
         let word = board.path_to_word(&vec![3, 1, 0, 2]);
 
         assert_eq!(word, "fred");
     }
+
+    #[test]
+    fn qu_tile() {
+        let dict: Dict = ["quit", "qat", "qi", "quip", "tip", "suq"]
+            .iter()
+            .copied()
+            .collect();
+        let options = ParseOptions {
+            qu_tiles: true,
+            ..ParseOptions::default()
+        };
+        let board = Board::parse_with("qit abp", &options).unwrap();
+
+        assert!(board.qu_tiles());
+        assert_eq!(board.words(&dict), vec!["quip", "quit", "tip"]);
+        assert_eq!(board.find_word("quit"), Some(vec![0, 1, 2]));
+        assert_eq!(board.find_word("QUIP"), Some(vec![0, 1, 5]));
+        assert_eq!(board.find_word("qat"), None);
+        assert_eq!(board.find_word("qit"), None);
+        assert_eq!(board.find_word("qi"), None);
+        assert_eq!(board.path_to_word(&vec![0, 1, 2]), "quit");
+        assert_eq!(
+            board.validate_word(&vec![0, 1, 5], &dict),
+            Ok("quip".to_string())
+        );
+
+        // without the option, q is a plain letter
+        let board: Board = "qit abp".parse().unwrap();
+
+        assert_eq!(board.words(&dict), vec!["qi", "tip"]);
+        assert_eq!(board.find_word("quit"), None);
+        assert_eq!(board.find_word("qi"), Some(vec![0, 1]));
+        assert_eq!(board.path_to_word(&vec![0, 1, 2]), "qit");
+    }
+
+    #[test]
+    fn cells() {
+        let board: Board = "bat tan".parse().unwrap();
+        let cells = CellPath(vec![Cell { row: 1, col: 2 }, Cell { row: 0, col: 1 }]);
+
+        assert_eq!(board.cell(4), Some(Cell { row: 1, col: 1 }));
+        assert_eq!(board.cell(6), None);
+        assert_eq!(board.pos(Cell { row: 0, col: 2 }), Some(2));
+        assert_eq!(board.pos(Cell { row: 0, col: 3 }), None);
+        assert_eq!(board.cell_path(&vec![5, 1]), Some(cells.clone()));
+        assert_eq!(board.cell_path(&vec![5, 9]), None);
+        assert_eq!(board.path_from_cells(&cells), Some(vec![5, 1]));
+        assert_eq!(cells.to_string(), "1:2 0:1");
+        assert_eq!("1:2".parse(), Ok(Cell { row: 1, col: 2 }));
+        assert_eq!(
+            "1-2".parse::<Cell>(),
+            Err(CellParseError("1-2".to_string()))
+        );
+    }
 }
 
 // test Graph
@@ -289,6 +1101,7 @@ mod test_dict {
     fn make_dict(end: bool, list: Vec<(char, Dict)>) -> Dict {
         Dict {
             end,
+            height: list.iter().map(|(_, d)| d.height + 1).max().unwrap_or(0),
             next: list.into_iter().collect(),
         }
     }
@@ -297,7 +1110,7 @@ mod test_dict {
     fn add_words() {
         let dict: Dict = ["an", "and", "ant", "anti", "bad", "bat", "bot", "boy"]
             .iter()
-            .map(|s| *s)
+            .copied()
             .collect();
 
         let expected: Dict = make_dict(
@@ -352,4 +1165,15 @@ mod test_dict {
 
         assert_eq!(dict, expected);
     }
+
+    #[test]
+    fn contains() {
+        let dict: Dict = ["an", "ant", "bat"].iter().copied().collect();
+
+        assert!(dict.contains("an"));
+        assert!(dict.contains("ant"));
+        assert!(!dict.contains("a"));
+        assert!(!dict.contains("anti"));
+        assert!(!dict.contains(""));
+    }
 }
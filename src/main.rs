@@ -1,28 +1,746 @@
+mod cli;
 mod dictionary;
+mod output;
+mod play;
+mod serve;
 
+use cli::Args;
+use output::{Format, Sort};
 use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::net::TcpListener;
+use std::path::Path;
 use std::process;
-use wordsearch::{Board, Dict};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wordsearch::analysis::{self, Difficulty, Stats, Usage};
+use wordsearch::dice::Dice;
+use wordsearch::lang::{Language, Registry};
+use wordsearch::parallel;
+use wordsearch::rack::{LetterValues, Play, Rack, RackOrder};
+use wordsearch::render::Render;
+use wordsearch::rng::Rng;
+use wordsearch::{Board, NonLetters, ParseOptions};
 
-fn main() {
-    let args = env::args();
+// Exit codes
+const EXIT_OK: i32 = 0;
+const EXIT_ERROR: i32 = 1;
+const EXIT_REJECTED: i32 = 2;
+
+// Lines read at a time by `batch`
+const BATCH_BLOCK: usize = 1024;
+
+const USAGE: &str = "\
+Usage: wordsearch <command> [options]
+
+Commands:
+  solve <board>           list the words on a board
+  generate                roll a random board
+  check <board> <word>... check guesses against a board
+  stats <board>           rate a board and show how its cells are used
+  batch [file]            solve one board per line of a file or stdin
+  play                    play a timed game on a random board
+  serve                   answer solve and check requests over HTTP
+  rack <letters>          list the words a rack of letters can make
+
+A board is given as rows separated by spaces, e.g. \"abcd efgh ijkl mnop\".
+`wordsearch <board>` is short for `wordsearch solve <board>`.
+Run `wordsearch <command> --help` for a command's options.
+
+Exit codes: 0 success, 1 error, 2 some input was rejected (check, batch).
+";
+
+const LANG_HELP: &str = "  --lang <name>           language to play in (default: en-us)
+  --lang-dir <dir>        directory of language packs to add
+";
+
+const SOLVE_HELP: &str = "\
+Usage: wordsearch solve <board> [options]
+
+Options:
+  --format <format>       json, ndjson, csv, text or svg (default: a list of every path's word)
+  --highlight <words>     comma-separated words whose paths svg output draws
+  --sort <order>          alpha, length or score, with --format or --show-paths
+                          (default: alpha)
+  --threads <n>           threads to solve on, 0 for one per core (default: 1)
+  --show-paths            draw the board, then each word's path on it
+  --color                 highlight paths with ANSI colors
+  --void                  make non-letter cells holes instead of rejecting them
+  --qu                    read q cells as \"Qu\" tiles, as on rolled boards
+";
+
+const GENERATE_HELP: &str = "\
+Usage: wordsearch generate [options]
+
+A q on the board is the \"Qu\" tile; solve the board with --qu to read it so.
+
+Options:
+  --dice <dice>           classic, or a file with one die per line
+                          (default: the language's dice)
+  --seed <n>              seed for a reproducible board (default: the clock)
+  --width <n>             row width (default: the square the dice fill)
+";
+
+const PLAY_HELP: &str = "\
+Usage: wordsearch play [options]
+
+Rolls a board and gives you until the time is up to type the words you find,
+one per line. At the end it shows your score and the words you missed.
+
+Options:
+  --time <seconds>        length of the game (default: 180)
+  --dice <dice>           classic, or a file with one die per line
+                          (default: the language's dice)
+  --seed <n>              seed for a reproducible board (default: the clock)
+  --width <n>             row width (default: the square the dice fill)
+";
+
+const SERVE_HELP: &str = "\
+Usage: wordsearch serve [options]
+
+Serves a JSON API over HTTP on localhost, keeping the dictionary loaded:
+
+  GET /solve?board=<board>                the words on a board
+  GET /check?board=<board>&word=<word>    check a guess
+  GET /validate?board=<board>&path=<r:c,..>
+                                          check a traced path of row:col cells
+                                          (or cell numbers)
+  GET /generate?seed=<n>&width=<n>        roll a board
+
+Add qu=1 to read q cells as \"Qu\" tiles, as on rolled boards.
+
+Options:
+  --port <n>              port to listen on (default: 8080)
+  --host <address>        address to listen on (default: 127.0.0.1)
+";
+
+const CHECK_HELP: &str = "\
+Usage: wordsearch check <board> <word>... [options]
+
+Checks that each word can be traced on the board and is in the dictionary.
+
+Options:
+  --void                  make non-letter cells holes instead of rejecting them
+  --qu                    read q cells as \"Qu\" tiles, as on rolled boards
+";
+
+const STATS_HELP: &str = "\
+Usage: wordsearch stats <board> [options]
+
+Difficulty options (0 to 100, from scarcity, word length and obscurity):
+  --saturation <n>        word count at which scarcity is zero (default: 100)
+  --scarcity-weight <x>   weight of scarcity (default: 1)
+  --length-weight <x>     weight of average word length (default: 1)
+  --obscurity-weight <x>  weight of uncommon words, with --frequency (default: 1)
+  --easy-below <x>        scores below this are easy (default: 35)
+  --hard-from <x>         scores from this up are hard (default: 65)
+
+Options:
+  --frequency <file>      word frequency list, most common first
+  --common <n>            how many words of the list count as common (default: 10000)
+  --heatmap               show how many words pass through and start on each cell
+  --void                  make non-letter cells holes instead of rejecting them
+  --qu                    read q cells as \"Qu\" tiles, as on rolled boards
+";
+
+const RACK_HELP: &str = "\
+Usage: wordsearch rack <letters> [options]
+
+Lists every word in the dictionary that can be made from the letters, each
+used at most once, with its value. A `?` or `_` is a blank, which can stand for
+any letter but is worth nothing.
+
+Options:
+  --sort <order>          alpha, length or value (default: alpha)
+  --values <file>         letter values, lines of `letter value`
+                          (default: English Scrabble values)
+  --min <n>               shortest word to list (default: 2)
+";
+
+const BATCH_HELP: &str = "\
+Usage: wordsearch batch [file] [options]
+
+Solves one board per line of the file, or of stdin if there is none or it is
+`-`, and writes one record per board. A line that can't be parsed gets an
+error record and the batch carries on. Blank lines are skipped.
+
+Options:
+  --format <format>       ndjson or csv (default: ndjson)
+  --sort <order>          alpha, length or score (default: alpha)
+  --threads <n>           threads to solve on, 0 for one per core (default: 1)
+  --void                  make non-letter cells holes instead of rejecting them
+  --qu                    read q cells as \"Qu\" tiles, as on rolled boards
+";
+
+fn fail(kind: &str, message: impl Display) -> ! {
+    eprintln!("{} error: {}", kind, message);
+    process::exit(EXIT_ERROR);
+}
+
+fn parse_args(args: Vec<String>, options: &[&str], switches: &[&str], help: &str) -> Args {
+    let options: Vec<&str> = options
+        .iter()
+        .chain(&["--lang", "--lang-dir"])
+        .copied()
+        .collect();
+    let args =
+        Args::parse(args, &options, switches).unwrap_or_else(|message| fail("Argument", message));
+    if args.has("--help") {
+        print!("{}{}", help, LANG_HELP);
+        process::exit(EXIT_OK);
+    }
+    args
+}
+
+fn en_us() -> Language {
+    Language {
+        dice: Some(Dice::classic()),
+        ..Language::new("en-us", dictionary::WORDS.iter().copied())
+    }
+}
+
+fn load_language(args: &Args) -> Language {
+    let mut registry = Registry::new();
+    registry.add_builtin("en-us", en_us);
+    if let Some(dir) = args.value("--lang-dir") {
+        if let Err(error) = registry.scan(Path::new(dir)) {
+            fail("File", format!("{}: {}", dir, error));
+        }
+    }
+    registry
+        .load(args.value("--lang").unwrap_or("en-us"))
+        .unwrap_or_else(|error| fail("Language", error))
+}
+
+fn parse_options(args: &Args) -> ParseOptions {
+    ParseOptions {
+        non_letters: if args.has("--void") {
+            NonLetters::Void
+        } else {
+            NonLetters::Reject
+        },
+        qu_tiles: args.has("--qu"),
+        ..ParseOptions::default()
+    }
+}
+
+fn parse_board(args: &Args, language: &Language) -> Board {
+    let board = args
+        .positional
+        .first()
+        .unwrap_or_else(|| fail("Argument", "missing board"));
+    let board = Board::parse_with(board, &parse_options(args))
+        .unwrap_or_else(|error| fail("Argument", error));
+    if let Err(error) = language.check_board(&board) {
+        fail("Argument", error);
+    }
+    board
+}
+
+fn parse_threads(args: &Args) -> usize {
+    match args.parsed("--threads") {
+        Ok(Some(0)) => parallel::default_threads(),
+        Ok(Some(n)) => n,
+        Ok(None) => 1,
+        Err(message) => fail("Argument", message),
+    }
+}
+
+fn solve(args: Vec<String>) -> i32 {
+    let args = parse_args(
+        args,
+        &["--format", "--sort", "--threads", "--highlight"],
+        &["--show-paths", "--color", "--void", "--qu"],
+        SOLVE_HELP,
+    );
+    if args.positional.len() > 1 {
+        fail(
+            "Argument",
+            format!("unexpected argument: {}", args.positional[1]),
+        );
+    }
+    let format: Option<Format> = args.value("--format").map(|format| {
+        format
+            .parse()
+            .unwrap_or_else(|message| fail("Argument", message))
+    });
+    let sort: Sort = args
+        .value("--sort")
+        .unwrap_or("alpha")
+        .parse()
+        .unwrap_or_else(|message| fail("Argument", message));
+    // the default output is every path's word in search order, unsorted
+    if args.value("--sort").is_some() && format.is_none() && !args.has("--show-paths") {
+        fail("Argument", "--sort needs --format or --show-paths");
+    }
+
+    let threads = parse_threads(&args);
+    let language = load_language(&args);
+    let board = parse_board(&args, &language);
+    let paths = board.search_parallel(&language.dict, threads);
+
+    if args.has("--show-paths") {
+        if format.is_some() {
+            fail("Argument", "--show-paths can't be used with --format");
+        }
+        let render = Render {
+            color: args.has("--color"),
+        };
+        let mut found = output::found_words(&board, &paths, &language.scores);
+        output::sort(&mut found, sort);
+
+        print!("{}", render.board(&board));
+        for f in found {
+            println!("\n{} ({})", f.word, f.score);
+            print!("{}", render.path(&board, &f.path));
+        }
+        return EXIT_OK;
+    }
+
+    match format {
+        Some(Format::Svg) => {
+            // svg draws the board, and only the paths asked for
+            let highlight = args.value("--highlight").unwrap_or("");
+            let found: Vec<output::Found> = highlight
+                .split(',')
+                .filter(|word| !word.is_empty())
+                .map(|word| match board.find_word(word) {
+                    Some(path) => output::Found {
+                        word: word.to_string(),
+                        score: language.scores.score_word(word),
+                        path,
+                    },
+                    None => fail("Argument", format!("{} is not on the board", word)),
+                })
+                .collect();
+            print!("{}", output::render(Format::Svg, &board, &found));
+        }
+        Some(format) => {
+            let mut found = output::found_words(&board, &paths, &language.scores);
+            output::sort(&mut found, sort);
+            print!("{}", output::render(format, &board, &found));
+        }
+        None => {
+            let words: Vec<String> = paths.iter().map(|path| board.path_to_word(path)).collect();
+            println!("{:?}", words);
+        }
+    }
+    EXIT_OK
+}
+
+// The dice named by --dice, or else the language's.
+fn pick_dice(args: &Args, language_dice: impl FnOnce() -> Option<Dice>) -> Dice {
+    match args.value("--dice") {
+        Some("classic") => Dice::classic(),
+        Some(path) => match fs::read_to_string(path) {
+            Ok(dice) => Dice::parse(&dice.to_lowercase()),
+            Err(error) => fail("File", format!("{}: {}", path, error)),
+        },
+        None => {
+            language_dice().unwrap_or_else(|| fail("Language", "language has no dice; use --dice"))
+        }
+    }
+}
+
+// Rolls a board string from the dice, using --seed and --width.
+fn roll(args: &Args, dice: &Dice, language: &Language) -> String {
+    let seed = args
+        .parsed("--seed")
+        .unwrap_or_else(|message| fail("Argument", message))
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or(0)
+        });
+    let width = args
+        .parsed("--width")
+        .unwrap_or_else(|message| fail("Argument", message))
+        .or_else(|| dice.square_width())
+        .unwrap_or_else(|| fail("Argument", "dice don't fill a square; use --width"));
+
+    let board = dice.roll(&mut Rng::new(seed), width);
+    match Board::parse_with(&board, &ParseOptions::rolled()) {
+        Ok(parsed) => {
+            if let Err(error) = language.check_board(&parsed) {
+                fail("Dice", error);
+            }
+        }
+        Err(error) => fail("Dice", error),
+    }
+    board
+}
+
+fn generate(args: Vec<String>) -> i32 {
+    let args = parse_args(args, &["--dice", "--seed", "--width"], &[], GENERATE_HELP);
+    if let Some(arg) = args.positional.first() {
+        fail("Argument", format!("unexpected argument: {}", arg));
+    }
+
+    let language = load_language(&args);
+    let dice = pick_dice(&args, || language.dice.clone());
+    println!("{}", roll(&args, &dice, &language));
+    EXIT_OK
+}
+
+fn play(args: Vec<String>) -> i32 {
+    let args = parse_args(
+        args,
+        &["--dice", "--seed", "--width", "--time"],
+        &[],
+        PLAY_HELP,
+    );
+    if let Some(arg) = args.positional.first() {
+        fail("Argument", format!("unexpected argument: {}", arg));
+    }
+    let seconds = args
+        .parsed("--time")
+        .unwrap_or_else(|message| fail("Argument", message))
+        .unwrap_or(180);
+
+    let language = load_language(&args);
+    let dice = pick_dice(&args, || language.dice.clone());
+    let board = Board::parse_with(&roll(&args, &dice, &language), &ParseOptions::rolled())
+        .unwrap_or_else(|error| fail("Dice", error));
+
+    let stdout = io::stdout();
+    play::run(
+        &board,
+        &language,
+        seconds,
+        BufReader::new(io::stdin()),
+        &mut stdout.lock(),
+    );
+    EXIT_OK
+}
+
+fn check(args: Vec<String>) -> i32 {
+    let args = parse_args(args, &[], &["--void", "--qu"], CHECK_HELP);
+    if args.positional.len() < 2 {
+        fail("Argument", "missing word");
+    }
+    let language = load_language(&args);
+    let board = parse_board(&args, &language);
+
+    let mut code = EXIT_OK;
+    for word in &args.positional[1..] {
+        match board.find_word(word) {
+            Some(path) if language.dict.contains(word) => {
+                let cells = board.cell_path(&path).unwrap_or_default();
+                println!("{}: ok ({})", word, cells);
+            }
+            Some(_) => {
+                println!("{}: not in dictionary", word);
+                code = EXIT_REJECTED;
+            }
+            None => {
+                println!("{}: not on board", word);
+                code = EXIT_REJECTED;
+            }
+        }
+    }
+    code
+}
 
-    let board_str = args.skip(1).next().unwrap_or_else(|| {
-        eprintln!("Argument error: missing argument");
-        process::exit(1);
+// The default difficulty with any of its settings given as options.
+fn parse_difficulty(args: &Args) -> Difficulty {
+    let defaults = Difficulty::default();
+    let option = |name: &str, default: f64| -> f64 {
+        args.parsed(name)
+            .unwrap_or_else(|message| fail("Argument", message))
+            .unwrap_or(default)
+    };
+    let weight = |name: &str, default: f64| -> f64 {
+        let weight = option(name, default);
+        if !(weight.is_finite() && weight >= 0.0) {
+            fail("Argument", format!("{} must be a number from 0 up", name));
+        }
+        weight
+    };
+    let threshold = |name: &str, default: f64| -> f64 {
+        let threshold = option(name, default);
+        if !(0.0..=100.0).contains(&threshold) {
+            fail("Argument", format!("{} must be from 0 to 100", name));
+        }
+        threshold
+    };
+    let difficulty = Difficulty {
+        saturation: args
+            .parsed("--saturation")
+            .unwrap_or_else(|message| fail("Argument", message))
+            .unwrap_or(defaults.saturation),
+        scarcity_weight: weight("--scarcity-weight", defaults.scarcity_weight),
+        length_weight: weight("--length-weight", defaults.length_weight),
+        obscurity_weight: weight("--obscurity-weight", defaults.obscurity_weight),
+        easy_below: threshold("--easy-below", defaults.easy_below),
+        hard_from: threshold("--hard-from", defaults.hard_from),
+    };
+    if difficulty.easy_below > difficulty.hard_from {
+        fail("Argument", "--easy-below must not be above --hard-from");
+    }
+    difficulty
+}
+
+fn stats(args: Vec<String>) -> i32 {
+    let args = parse_args(
+        args,
+        &[
+            "--frequency",
+            "--common",
+            "--saturation",
+            "--scarcity-weight",
+            "--length-weight",
+            "--obscurity-weight",
+            "--easy-below",
+            "--hard-from",
+        ],
+        &["--heatmap", "--void", "--qu"],
+        STATS_HELP,
+    );
+    if args.positional.len() > 1 {
+        fail(
+            "Argument",
+            format!("unexpected argument: {}", args.positional[1]),
+        );
+    }
+    let limit = args
+        .parsed("--common")
+        .unwrap_or_else(|message| fail("Argument", message))
+        .unwrap_or(10_000);
+    let common = args.value("--frequency").map(|path| {
+        let list = fs::read_to_string(path)
+            .unwrap_or_else(|error| fail("File", format!("{}: {}", path, error)));
+        analysis::common_words(&list, limit)
     });
 
-    let board: Board = match board_str.parse() {
-        Ok(board) => board,
-        Err(message) => {
-            eprintln!("Argument error: {}", message);
-            process::exit(1);
+    let language = load_language(&args);
+    let board = parse_board(&args, &language);
+    let paths = board.search(&language.dict);
+
+    let stats = Stats::new(&board.words(&language.dict), common.as_ref());
+    let (level, score) = parse_difficulty(&args).rate(&stats);
+
+    println!("words: {}", stats.word_count);
+    println!("average length: {:.2}", stats.average_length);
+    println!("longest: {}", stats.longest.unwrap_or_default());
+    if let Some(share) = stats.common_share {
+        println!("common: {:.0}%", share * 100.0);
+    }
+    println!("difficulty: {} ({:.1})", level, score);
+
+    if args.has("--heatmap") {
+        let usage = Usage::new(&board, &paths);
+
+        println!("words through each cell:");
+        print!("{}", usage.through.heatmap());
+        println!("words starting on each cell:");
+        print!("{}", usage.starts.heatmap());
+    }
+    EXIT_OK
+}
+
+fn rack(args: Vec<String>) -> i32 {
+    let args = parse_args(args, &["--sort", "--values", "--min"], &[], RACK_HELP);
+    let letters = match args.positional.as_slice() {
+        [letters] => letters,
+        [] => fail("Argument", "missing letters"),
+        [_, arg, ..] => fail("Argument", format!("unexpected argument: {}", arg)),
+    };
+    let min: usize = args
+        .parsed("--min")
+        .unwrap_or_else(|message| fail("Argument", message))
+        .unwrap_or(2);
+    let values = match args.value("--values") {
+        Some(path) => {
+            let values = fs::read_to_string(path)
+                .unwrap_or_else(|error| fail("File", format!("{}: {}", path, error)));
+            LetterValues::parse(&values)
+                .unwrap_or_else(|line| fail("File", format!("{}: invalid line {}", path, line)))
         }
+        None => LetterValues::scrabble(),
+    };
+    let order = match args.value("--sort").unwrap_or("alpha") {
+        "alpha" => RackOrder::Alpha,
+        "length" => RackOrder::Length,
+        "value" => RackOrder::Value(values.clone()),
+        sort => fail("Argument", format!("unknown sort: {}", sort)),
+    };
+
+    let language = load_language(&args);
+    let mut plays: Vec<Play> = Rack::parse(letters)
+        .words(&language.dict)
+        .into_iter()
+        .filter(|play| play.word.chars().count() >= min)
+        .collect();
+    order.sort(&mut plays);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    for play in &plays {
+        let _ = writeln!(out, "{} {}", play.word, values.score(play));
+    }
+    EXIT_OK
+}
+
+fn batch(args: Vec<String>) -> i32 {
+    let args = parse_args(
+        args,
+        &["--format", "--sort", "--threads"],
+        &["--void", "--qu"],
+        BATCH_HELP,
+    );
+    if args.positional.len() > 1 {
+        fail(
+            "Argument",
+            format!("unexpected argument: {}", args.positional[1]),
+        );
+    }
+    let format = match args.value("--format").unwrap_or("ndjson").parse() {
+        Ok(Format::Ndjson) => Format::Ndjson,
+        Ok(Format::Csv) => Format::Csv,
+        Ok(_) => fail("Argument", "batch output is ndjson or csv"),
+        Err(message) => fail("Argument", message),
+    };
+    let sort: Sort = args
+        .value("--sort")
+        .unwrap_or("alpha")
+        .parse()
+        .unwrap_or_else(|message| fail("Argument", message));
+
+    let input: Box<dyn BufRead> = match args.positional.first().map(String::as_str) {
+        None | Some("-") => Box::new(BufReader::new(io::stdin())),
+        Some(path) => match fs::File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => fail("File", format!("{}: {}", path, error)),
+        },
+    };
+    let threads = parse_threads(&args);
+    let options = parse_options(&args);
+    let language = load_language(&args);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let write = |out: &mut BufWriter<_>, record: String| {
+        if let Err(error) = out.write_all(record.as_bytes()) {
+            fail("Output", error);
+        }
+    };
+    if format == Format::Csv {
+        write(&mut out, output::BATCH_CSV_HEADER.to_string());
+    }
+
+    // Lines are read in blocks, so that a block's boards can be solved across
+    // threads while records are still written in input order.
+    let solve_line = |(number, line): &(usize, Result<String, String>)| {
+        let board_str = match line {
+            Ok(board_str) => board_str,
+            Err(lossy) => {
+                let solved: output::Solved = Err("line is not UTF-8".to_string());
+                return (*number, lossy.clone(), solved);
+            }
+        };
+        let solved = Board::parse_with(board_str, &options)
+            .map_err(|error| error.to_string())
+            .and_then(|board| {
+                language
+                    .check_board(&board)
+                    .map_err(|error| error.to_string())?;
+                Ok(board)
+            })
+            .map(|board| {
+                let paths = board.search(&language.dict);
+                let mut found = output::found_words(&board, &paths, &language.scores);
+                output::sort(&mut found, sort);
+                found
+            });
+        (*number, board_str.clone(), solved)
     };
 
-    let dict: Dict = dictionary::WORDS.iter().map(|s| *s).collect();
-    let paths = board.search(&dict);
-    let words: Vec<String> = paths.iter().map(|path| board.path_to_word(path)).collect();
+    let mut code = EXIT_OK;
+    let mut lines = cli::Lines::new(input);
+    loop {
+        let mut block = Vec::with_capacity(BATCH_BLOCK);
+        for line in lines.by_ref() {
+            let (number, line) = line.unwrap_or_else(|error| fail("Input", error));
+            let line = match line {
+                Ok(line) => Ok(line.trim().to_string()),
+                Err(lossy) => Err(lossy.trim().to_string()),
+            };
+            if !matches!(&line, Ok(line) if line.is_empty()) {
+                block.push((number, line));
+            }
+            if block.len() == BATCH_BLOCK {
+                break;
+            }
+        }
+        if block.is_empty() {
+            break;
+        }
+
+        for (number, board_str, solved) in parallel::map(&block, threads, solve_line) {
+            if solved.is_err() {
+                code = EXIT_REJECTED;
+            }
+            let record = match format {
+                Format::Csv => output::batch_csv(number, &board_str, &solved),
+                _ => output::batch_json(number, &board_str, &solved),
+            };
+            write(&mut out, record);
+        }
+    }
+    if let Err(error) = out.flush() {
+        fail("Output", error);
+    }
+    code
+}
+
+fn serve(args: Vec<String>) -> i32 {
+    let args = parse_args(args, &["--port", "--host"], &[], SERVE_HELP);
+    if let Some(arg) = args.positional.first() {
+        fail("Argument", format!("unexpected argument: {}", arg));
+    }
+    let port: u16 = args
+        .parsed("--port")
+        .unwrap_or_else(|message| fail("Argument", message))
+        .unwrap_or(8080);
+    let host = args.value("--host").unwrap_or("127.0.0.1");
+
+    let language = Arc::new(load_language(&args));
+    let listener = TcpListener::bind((host, port))
+        .unwrap_or_else(|error| fail("Server", format!("{}:{}: {}", host, port, error)));
+    eprintln!("Listening on http://{}:{}", host, port);
+    serve::serve(listener, language);
+    EXIT_ERROR
+}
 
-    println!("{:?}", words);
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let command = match args.first().map(String::as_str) {
+        Some("solve") | Some("generate") | Some("check") | Some("stats") | Some("batch")
+        | Some("play") | Some("serve") | Some("rack") => args.remove(0),
+        Some("--help") | Some("-h") | Some("help") => {
+            print!("{}", USAGE);
+            process::exit(EXIT_OK);
+        }
+        Some(_) => "solve".to_string(),
+        None => {
+            eprint!("{}", USAGE);
+            fail("Argument", "missing command");
+        }
+    };
+
+    let code = match command.as_str() {
+        "generate" => generate(args),
+        "check" => check(args),
+        "stats" => stats(args),
+        "batch" => batch(args),
+        "play" => play(args),
+        "serve" => serve(args),
+        "rack" => rack(args),
+        _ => solve(args),
+    };
+    process::exit(code);
 }
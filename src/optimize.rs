@@ -0,0 +1,375 @@
+use crate::rng::Rng;
+use crate::score::ScoreTable;
+use crate::{Board, Dict, QU_TILE};
+
+// Objective
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Objective {
+    /// The number of distinct words on the board.
+    WordCount,
+    /// The total score of the distinct words on the board.
+    Score(ScoreTable),
+}
+
+impl Objective {
+    /// Scores `board` by listing its words. `Counter` gives the same result
+    /// much faster when evaluating many boards.
+    pub fn evaluate(&self, board: &Board, dict: &Dict) -> u32 {
+        let words = board.words(dict);
+        match self {
+            Objective::WordCount => words.len() as u32,
+            Objective::Score(table) => table.score_words(words.iter().map(String::as_str)),
+        }
+    }
+}
+
+// Counter
+
+/// Evaluates objectives on many boards with the same grid, as when only
+/// their letters change, without collecting any paths or words.
+///
+/// The dictionary is flattened once into one array of edges, where each
+/// edge holds everything about the node it leads to and a node's edges sit
+/// next to each other, so stepping through the trie reads a single array.
+/// Each evaluation walks the board with a visited bitmask, and stamps each
+/// word-end node it reaches with the evaluation's number, so a word found
+/// along several paths counts once. Boards of more than 128 cells are
+/// evaluated by listing their words in `dict` instead.
+pub struct Counter<'a> {
+    dict: &'a Dict,
+    root: Node,
+    edges: Vec<Edge>,
+    // by position; empty for void cells
+    neighbors: Vec<Vec<usize>>,
+    tiles: Vec<Option<char>>,
+    qu_tiles: bool,
+    // per edge, the last evaluation that reached the node it leads to
+    seen: Vec<u32>,
+    stamp: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Node {
+    end: bool,
+    length: u32,
+    // this node's range of `edges`
+    first: u32,
+    last: u32,
+}
+
+struct Edge {
+    letter: char,
+    node: Node,
+}
+
+const MAX_CELLS: usize = 128;
+
+impl<'a> Counter<'a> {
+    pub fn new(dict: &'a Dict, board: &Board) -> Counter<'a> {
+        let mut edges = vec![];
+        let root = flatten(dict, 0, &mut edges);
+        let size = (board.width * board.height) as usize;
+        let mut neighbors = vec![vec![]; size];
+        for (&pos, next) in board.grid.0.iter() {
+            neighbors[pos as usize] = next.iter().map(|&n| n as usize).collect();
+        }
+        Counter {
+            dict,
+            root,
+            seen: vec![0; edges.len()],
+            edges,
+            neighbors,
+            tiles: vec![None; size],
+            qu_tiles: board.qu_tiles,
+            stamp: 0,
+        }
+    }
+
+    /// The same as `objective.evaluate(board, dict)`, for a board with the
+    /// grid of the one the counter was made with.
+    pub fn evaluate(&mut self, board: &Board, objective: &Objective) -> u32 {
+        debug_assert_eq!(self.tiles.len(), (board.width * board.height) as usize);
+        if self.tiles.len() > MAX_CELLS {
+            return objective.evaluate(board, self.dict);
+        }
+        self.stamp += 1;
+        for (pos, tile) in self.tiles.iter_mut().enumerate() {
+            *tile = board.chars.get(&(pos as u32)).copied();
+        }
+        self.qu_tiles = board.qu_tiles;
+        let table = match objective {
+            Objective::WordCount => None,
+            Objective::Score(table) => Some(table),
+        };
+        let mut total = 0;
+        for pos in 0..self.tiles.len() {
+            if let Some(edge) = self.step(self.root, pos) {
+                self.walk(edge, pos, 0, table, &mut total);
+            }
+        }
+        total
+    }
+
+    // The edge from `node` spelling the tile at `pos`, if there is one.
+    fn step(&self, node: Node, pos: usize) -> Option<usize> {
+        let child = |node: Node, c: char| {
+            (node.first..node.last).find(|&e| self.edges[e as usize].letter == c)
+        };
+        let edge = match self.tiles[pos]? {
+            QU_TILE if self.qu_tiles => {
+                let q = child(node, QU_TILE)?;
+                child(self.edges[q as usize].node, 'u')?
+            }
+            c => child(node, c)?,
+        };
+        Some(edge as usize)
+    }
+
+    // Visits the node reached over `edge` by stepping onto `pos`.
+    fn walk(
+        &mut self,
+        edge: usize,
+        pos: usize,
+        visited: u128,
+        table: Option<&ScoreTable>,
+        total: &mut u32,
+    ) {
+        let node = self.edges[edge].node;
+        if node.end && self.seen[edge] != self.stamp {
+            self.seen[edge] = self.stamp;
+            *total += match table {
+                Some(table) => table.score_length(node.length as usize),
+                None => 1,
+            };
+        }
+        if node.first == node.last {
+            return;
+        }
+
+        let visited = visited | 1 << pos;
+        for i in 0..self.neighbors[pos].len() {
+            let next = self.neighbors[pos][i];
+            if visited & 1 << next == 0 {
+                if let Some(edge) = self.step(node, next) {
+                    self.walk(edge, next, visited, table, total);
+                }
+            }
+        }
+    }
+}
+
+// Adds the edges of `dict` and everything below it to `edges`, returning
+// its node. Its own edges are reserved before its children add theirs.
+fn flatten(dict: &Dict, length: u32, edges: &mut Vec<Edge>) -> Node {
+    let first = edges.len();
+    edges.extend(dict.next.keys().map(|&letter| Edge {
+        letter,
+        node: Node {
+            end: false,
+            length: 0,
+            first: 0,
+            last: 0,
+        },
+    }));
+    for (i, child) in dict.next.values().enumerate() {
+        edges[first + i].node = flatten(child, length + 1, edges);
+    }
+    Node {
+        end: dict.end,
+        length,
+        first: first as u32,
+        last: (first + dict.next.len()) as u32,
+    }
+}
+
+// Anneal
+
+/// Simulated annealing over the letters of a board.
+///
+/// Each iteration replaces one cell with a random letter from `alphabet` and
+/// re-scores the board. Improvements are always kept; a worse board is kept
+/// with probability `exp(delta / t)`, where `t` cools linearly from
+/// `temperature` to zero. A `temperature` of zero is plain hill climbing.
+/// Boards are scored with a `Counter`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Anneal {
+    pub alphabet: Vec<char>,
+    pub iterations: u32,
+    pub temperature: f64,
+    pub seed: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub best: Board,
+    pub best_score: u32,
+    /// The score of the current board after each iteration, starting with the
+    /// score of the initial board.
+    pub history: Vec<u32>,
+}
+
+impl Anneal {
+    pub fn new(alphabet: &str) -> Anneal {
+        Anneal {
+            alphabet: alphabet.chars().collect(),
+            iterations: 10_000,
+            temperature: 2.0,
+            seed: 0,
+        }
+    }
+
+    pub fn run(&self, board: Board, dict: &Dict, objective: &Objective) -> Outcome {
+        let mut rng = Rng::new(self.seed);
+        let positions: Vec<u32> = board.cells().map(|(pos, _)| pos).collect();
+
+        let mut counter = Counter::new(dict, &board);
+        let mut current = board;
+        let mut current_score = counter.evaluate(&current, objective);
+        let mut best = current.clone();
+        let mut best_score = current_score;
+        let mut history = Vec::with_capacity(self.iterations as usize + 1);
+        history.push(current_score);
+
+        for i in 0..self.iterations {
+            let (&pos, &c) = match (rng.choose(&positions), rng.choose(&self.alphabet)) {
+                (Some(pos), Some(c)) => (pos, c),
+                _ => break,
+            };
+            let previous = current.set_char(pos, c).unwrap_or(c);
+            let score = counter.evaluate(&current, objective);
+
+            let t = self.temperature * (1.0 - i as f64 / self.iterations as f64);
+            let delta = score as f64 - current_score as f64;
+            let accept = delta >= 0.0 || (t > 0.0 && rng.unit() < (delta / t).exp());
+
+            if accept {
+                current_score = score;
+                if score > best_score {
+                    best = current.clone();
+                    best_score = score;
+                }
+            } else {
+                current.set_char(pos, previous);
+            }
+            history.push(current_score);
+        }
+
+        Outcome {
+            best,
+            best_score,
+            history,
+        }
+    }
+}
+
+// test Anneal
+
+#[cfg(test)]
+mod test_anneal {
+    use super::*;
+    use crate::{NonLetters, ParseOptions};
+
+    fn dict() -> Dict {
+        [
+            "an", "and", "ant", "anti", "bad", "banana", "bat", "bot", "boy", "tab", "tan",
+        ]
+        .iter()
+        .copied()
+        .collect()
+    }
+
+    #[test]
+    fn objective() {
+        let board: Board = "ba tn".parse().unwrap();
+
+        assert_eq!(Objective::WordCount.evaluate(&board, &dict()), 5);
+        assert_eq!(
+            Objective::Score(ScoreTable::boggle()).evaluate(&board, &dict()),
+            4
+        );
+    }
+
+    #[test]
+    fn counter() {
+        let mut dict = dict();
+        dict.add_word("quit");
+        dict.add_word("quint");
+        dict.add_word("qat");
+        let objectives = [Objective::WordCount, Objective::Score(ScoreTable::boggle())];
+
+        for qu_tiles in [false, true] {
+            let options = ParseOptions {
+                non_letters: NonLetters::Void,
+                qu_tiles,
+                ..ParseOptions::default()
+            };
+            let board = Board::parse_with("bat. qin. t.ab", &options).unwrap();
+            let mut counter = Counter::new(&dict, &board);
+            let mut current = board.clone();
+            let mut rng = Rng::new(5);
+
+            for _ in 0..200 {
+                let pos = rng.below(12) as u32;
+                current.set_char(
+                    pos,
+                    *rng.choose(&['a', 'b', 'd', 'i', 'n', 'o', 'q', 't', 'u'])
+                        .unwrap(),
+                );
+                for objective in objectives.iter() {
+                    assert_eq!(
+                        counter.evaluate(&current, objective),
+                        objective.evaluate(&current, &dict)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn counter_large_board() {
+        let dict = dict();
+        let rows: Vec<String> = (0..12).map(|_| "bantdobyadot".to_string()).collect();
+        let board: Board = rows.join(" ").parse().unwrap();
+        let mut counter = Counter::new(&dict, &board);
+        let mut current = board.clone();
+        let mut rng = Rng::new(3);
+        let objectives = [Objective::WordCount, Objective::Score(ScoreTable::boggle())];
+
+        for _ in 0..20 {
+            let pos = rng.below(144) as u32;
+            current.set_char(
+                pos,
+                *rng.choose(&['a', 'b', 'd', 'i', 'n', 'o', 't']).unwrap(),
+            );
+            for objective in objectives.iter() {
+                assert_eq!(
+                    counter.evaluate(&current, objective),
+                    objective.evaluate(&current, &dict)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn run() {
+        let board: Board = "xxx xxx".parse().unwrap();
+        let anneal = Anneal {
+            iterations: 500,
+            seed: 3,
+            ..Anneal::new("abdinot")
+        };
+
+        let outcome = anneal.run(board.clone(), &dict(), &Objective::WordCount);
+
+        assert_eq!(outcome.history.len(), 501);
+        assert_eq!(outcome.history[0], 0);
+        assert!(outcome.best_score > 0);
+        assert_eq!(outcome.best_score, *outcome.history.iter().max().unwrap());
+        assert_eq!(
+            Objective::WordCount.evaluate(&outcome.best, &dict()),
+            outcome.best_score
+        );
+        assert_eq!(outcome, anneal.run(board, &dict(), &Objective::WordCount));
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap as Map;
+use std::fmt::Write;
+use std::str::FromStr;
+use wordsearch::score::ScoreTable;
+use wordsearch::svg::Svg;
+use wordsearch::{Board, Path};
+
+// Found
+
+/// A distinct word found on a board, with the first path that spells it.
+#[derive(Debug, PartialEq)]
+pub struct Found {
+    pub word: String,
+    pub path: Path,
+    pub score: u32,
+}
+
+pub fn found_words(board: &Board, paths: &[Path], scores: &ScoreTable) -> Vec<Found> {
+    let mut words: Map<String, &Path> = Map::new();
+    for path in paths {
+        words.entry(board.path_to_word(path)).or_insert(path);
+    }
+    words
+        .into_iter()
+        .map(|(word, path)| Found {
+            score: scores.score_word(&word),
+            word,
+            path: path.clone(),
+        })
+        .collect()
+}
+
+// Sort
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sort {
+    Alpha,
+    Length,
+    Score,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alpha" => Ok(Sort::Alpha),
+            "length" => Ok(Sort::Length),
+            "score" => Ok(Sort::Score),
+            _ => Err(format!("unknown sort: {}", s)),
+        }
+    }
+}
+
+/// Sorts alphabetically, or longest or highest scoring first with ties in
+/// alphabetical order.
+pub fn sort(found: &mut [Found], sort: Sort) {
+    match sort {
+        Sort::Alpha => found.sort_by(|a, b| a.word.cmp(&b.word)),
+        Sort::Length => found.sort_by(|a, b| {
+            let length = |f: &Found| f.word.chars().count();
+            length(b).cmp(&length(a)).then_with(|| a.word.cmp(&b.word))
+        }),
+        Sort::Score => {
+            found.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.word.cmp(&b.word)))
+        }
+    }
+}
+
+// Format
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Json,
+    Ndjson,
+    Csv,
+    Text,
+    Svg,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            "csv" => Ok(Format::Csv),
+            "text" => Ok(Format::Text),
+            "svg" => Ok(Format::Svg),
+            _ => Err(format!("unknown format: {}", s)),
+        }
+    }
+}
+
+pub fn render(format: Format, board: &Board, found: &[Found]) -> String {
+    match format {
+        Format::Json => json(board, found) + "\n",
+        Format::Ndjson => found.iter().map(|f| json_record(board, f) + "\n").collect(),
+        Format::Csv => csv(board, found),
+        Format::Text => found.iter().map(|f| f.word.clone() + "\n").collect(),
+        Format::Svg => {
+            let paths: Vec<Path> = found.iter().map(|f| f.path.clone()).collect();
+            Svg::default().render(board, &paths)
+        }
+    }
+}
+
+/// A JSON array with a `json_record` for each word.
+pub fn json(board: &Board, found: &[Found]) -> String {
+    let records: Vec<String> = found.iter().map(|f| json_record(board, f)).collect();
+    format!("[{}]", records.join(","))
+}
+
+/// A JSON object with the `word`, its `length` and `score`, and its `path` as
+/// a list of `{"row", "col"}` objects.
+pub fn json_record(board: &Board, found: &Found) -> String {
+    let path: Vec<String> = board
+        .cell_path(&found.path)
+        .unwrap_or_default()
+        .0
+        .iter()
+        .map(|cell| format!("{{\"row\":{},\"col\":{}}}", cell.row, cell.col))
+        .collect();
+    format!(
+        "{{\"word\":{},\"length\":{},\"score\":{},\"path\":[{}]}}",
+        json_string(&found.word),
+        found.word.chars().count(),
+        found.score,
+        path.join(",")
+    )
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// CSV with a header row; the path is a space-separated list of `row:col`.
+pub fn csv(board: &Board, found: &[Found]) -> String {
+    let mut out = String::from("word,length,score,path\n");
+    for f in found {
+        let path = board.cell_path(&f.path).unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            csv_field(&f.word),
+            f.word.chars().count(),
+            f.score,
+            path
+        );
+    }
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// Batch
+
+/// The result of solving one line of a batch.
+pub type Solved = Result<Vec<Found>, String>;
+
+/// One line of NDJSON for a batch line: the `line` number and `board`, then
+/// either the `count`, total `score` and `words` found, or an `error`.
+pub fn batch_json(line: usize, board: &str, solved: &Solved) -> String {
+    match solved {
+        Ok(found) => {
+            let words: Vec<String> = found.iter().map(|f| json_string(&f.word)).collect();
+            format!(
+                "{{\"line\":{},\"board\":{},\"count\":{},\"score\":{},\"words\":[{}]}}\n",
+                line,
+                json_string(board),
+                found.len(),
+                found.iter().map(|f| f.score).sum::<u32>(),
+                words.join(",")
+            )
+        }
+        Err(error) => format!(
+            "{{\"line\":{},\"board\":{},\"error\":{}}}\n",
+            line,
+            json_string(board),
+            json_string(error)
+        ),
+    }
+}
+
+pub const BATCH_CSV_HEADER: &str = "line,board,count,score,words,error\n";
+
+/// One CSV row for a batch line; the words are separated by spaces.
+pub fn batch_csv(line: usize, board: &str, solved: &Solved) -> String {
+    match solved {
+        Ok(found) => {
+            let words: Vec<&str> = found.iter().map(|f| f.word.as_str()).collect();
+            format!(
+                "{},{},{},{},{},\n",
+                line,
+                csv_field(board),
+                found.len(),
+                found.iter().map(|f| f.score).sum::<u32>(),
+                csv_field(&words.join(" "))
+            )
+        }
+        Err(error) => format!("{},{},,,,{}\n", line, csv_field(board), csv_field(error)),
+    }
+}
+
+// test output
+
+#[cfg(test)]
+mod test_output {
+    use super::*;
+    use wordsearch::Dict;
+
+    fn solve() -> (Board, Vec<Found>) {
+        let dict: Dict = ["an", "ant", "bat", "tab", "banana"]
+            .iter()
+            .copied()
+            .collect();
+        let board: Board = "ba tn".parse().unwrap();
+        let found = found_words(&board, &board.search(&dict), &ScoreTable::boggle());
+        (board, found)
+    }
+
+    #[test]
+    fn found() {
+        let (_, found) = solve();
+
+        let words: Vec<&str> = found.iter().map(|f| f.word.as_str()).collect();
+        assert_eq!(words, vec!["an", "ant", "bat", "tab"]);
+        assert_eq!(found[2].path, vec![0, 1, 2]);
+        assert_eq!(found[0].score, 0);
+    }
+
+    #[test]
+    fn sorted() {
+        let (_, mut found) = solve();
+
+        sort(&mut found, Sort::Length);
+        let words: Vec<&str> = found.iter().map(|f| f.word.as_str()).collect();
+        assert_eq!(words, vec!["ant", "bat", "tab", "an"]);
+
+        sort(&mut found, Sort::Score);
+        assert_eq!(found[3].word, "an");
+
+        sort(&mut found, Sort::Alpha);
+        assert_eq!(found[0].word, "an");
+    }
+
+    #[test]
+    fn formats() {
+        let (board, found) = solve();
+
+        assert_eq!(
+            render(Format::Json, &board, &found[..1]),
+            "[{\"word\":\"an\",\"length\":2,\"score\":0,\"path\":[{\"row\":0,\"col\":1},{\"row\":1,\"col\":1}]}]\n"
+        );
+        assert_eq!(
+            render(Format::Csv, &board, &found[..2]),
+            "word,length,score,path\nan,2,0,0:1 1:1\nant,3,1,0:1 1:1 1:0\n"
+        );
+        assert_eq!(
+            render(Format::Ndjson, &board, &found[2..]),
+            "{\"word\":\"bat\",\"length\":3,\"score\":1,\"path\":[{\"row\":0,\"col\":0},{\"row\":0,\"col\":1},{\"row\":1,\"col\":0}]}\n\
+             {\"word\":\"tab\",\"length\":3,\"score\":1,\"path\":[{\"row\":1,\"col\":0},{\"row\":0,\"col\":1},{\"row\":0,\"col\":0}]}\n"
+        );
+        assert_eq!(render(Format::Text, &board, &found), "an\nant\nbat\ntab\n");
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(json_string("a\"b\\c\n\u{1}"), "\"a\\\"b\\\\c\\n\\u0001\"");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn batch() {
+        let (_, found) = solve();
+        let ok: Solved = Ok(found);
+        let err: Solved = Err("must have at least two rows, found 1".to_string());
+
+        assert_eq!(
+            batch_json(1, "ba tn", &ok),
+            "{\"line\":1,\"board\":\"ba tn\",\"count\":4,\"score\":3,\"words\":[\"an\",\"ant\",\"bat\",\"tab\"]}\n"
+        );
+        assert_eq!(
+            batch_json(2, "abc", &err),
+            "{\"line\":2,\"board\":\"abc\",\"error\":\"must have at least two rows, found 1\"}\n"
+        );
+        assert_eq!(batch_csv(1, "ba tn", &ok), "1,ba tn,4,3,an ant bat tab,\n");
+        assert_eq!(
+            batch_csv(2, "abc", &err),
+            "2,abc,,,,\"must have at least two rows, found 1\"\n"
+        );
+    }
+}
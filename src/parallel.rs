@@ -0,0 +1,56 @@
+use std::thread;
+
+/// The number of threads to use when none is given: one per available core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Maps `f` over `items` on up to `threads` scoped threads, each taking a
+/// contiguous chunk, and returns the results in the order of `items`.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = threads.max(1).min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk = items.len().div_ceil(threads);
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk)
+            .map(|part| scope.spawn(move || part.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect()
+    })
+}
+
+// test parallel
+
+#[cfg(test)]
+mod test_parallel {
+    use super::*;
+
+    #[test]
+    fn map_keeps_order() {
+        let items: Vec<u32> = (0..103).collect();
+
+        let expected: Vec<u32> = items.iter().map(|x| x * x).collect();
+
+        assert_eq!(map(&items, 4, |x| x * x), expected);
+        assert_eq!(map(&items, 1, |x| x * x), expected);
+        assert_eq!(map(&items, 500, |x| x * x), expected);
+        assert_eq!(map(&[] as &[u32], 4, |x| x * x), vec![]);
+    }
+}
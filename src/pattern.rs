@@ -0,0 +1,224 @@
+use crate::{fold_case, text, Dict};
+use std::collections::BTreeSet as Set;
+use std::error::Error;
+use std::fmt;
+
+// Pattern
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Letter(char),
+    // `?`
+    Any,
+    // `*`
+    Star,
+    // `[aeiou]`, or `[^aeiou]` if negated
+    Class { letters: Set<char>, negated: bool },
+}
+
+impl Token {
+    fn accepts(&self, c: char) -> bool {
+        match self {
+            Token::Letter(letter) => *letter == c,
+            Token::Any | Token::Star => true,
+            Token::Class { letters, negated } => letters.contains(&c) != *negated,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PatternError {
+    UnclosedClass { at: usize },
+    EmptyClass { at: usize },
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::UnclosedClass { at } => {
+                write!(f, "letter class at {} has no closing ]", at)
+            }
+            PatternError::EmptyClass { at } => write!(f, "letter class at {} is empty", at),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+fn parse(pattern: &str) -> Result<Vec<Token>, PatternError> {
+    let mut tokens = vec![];
+    let pattern = text::compose_latin(pattern);
+    let mut chars = pattern.chars().enumerate();
+    while let Some((at, c)) = chars.next() {
+        let token = match c {
+            '?' => Token::Any,
+            // runs of `*` match the same as one
+            '*' if tokens.last() == Some(&Token::Star) => continue,
+            '*' => Token::Star,
+            '[' => {
+                let mut letters = Set::new();
+                let mut negated = false;
+                let mut closed = false;
+                for (i, c) in chars.by_ref() {
+                    match c {
+                        ']' => {
+                            closed = true;
+                            break;
+                        }
+                        '^' if i == at + 1 => negated = true,
+                        c => {
+                            letters.insert(fold_case(c));
+                        }
+                    }
+                }
+                if !closed {
+                    return Err(PatternError::UnclosedClass { at });
+                }
+                if letters.is_empty() {
+                    return Err(PatternError::EmptyClass { at });
+                }
+                Token::Class { letters, negated }
+            }
+            c => Token::Letter(fold_case(c)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+impl Dict {
+    /// The words matching `pattern`, in alphabetical order, found as they
+    /// are needed.
+    ///
+    /// In a pattern `?` matches any one letter, `*` any run of letters,
+    /// including none, and `[aeiou]` any one of the letters in the brackets,
+    /// or with `[^aeiou]` any letter not in them. Other characters match
+    /// themselves, ignoring case.
+    pub fn matches(&self, pattern: &str) -> Result<Matches<'_>, PatternError> {
+        let tokens = parse(pattern)?;
+        let mut matches = Matches {
+            tokens,
+            stack: vec![],
+        };
+        let start = matches.close(Set::from([0]));
+        matches.stack.push((self, String::new(), start));
+        Ok(matches)
+    }
+}
+
+/// An iterator over the words matching a pattern. It walks the trie depth
+/// first, tracking every place in the pattern the word so far can reach, so
+/// each word is visited once however many ways `*` could match it.
+pub struct Matches<'a> {
+    tokens: Vec<Token>,
+    stack: Vec<(&'a Dict, String, Set<usize>)>,
+}
+
+impl<'a> Matches<'a> {
+    // Adds the places reached by letting a `*` match nothing.
+    fn close(&self, mut states: Set<usize>) -> Set<usize> {
+        let stars: Vec<usize> = states
+            .iter()
+            .copied()
+            .filter(|&i| self.tokens.get(i) == Some(&Token::Star))
+            .collect();
+        for i in stars {
+            states.insert(i + 1);
+        }
+        states
+    }
+
+    fn step(&self, states: &Set<usize>, c: char) -> Set<usize> {
+        let next = states
+            .iter()
+            .filter_map(|&i| match self.tokens.get(i) {
+                Some(Token::Star) => Some(i),
+                Some(token) if token.accepts(c) => Some(i + 1),
+                _ => None,
+            })
+            .collect();
+        self.close(next)
+    }
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        while let Some((dict, word, states)) = self.stack.pop() {
+            for (&c, next) in dict.next.iter().rev() {
+                let next_states = self.step(&states, c);
+                if !next_states.is_empty() {
+                    let mut next_word = word.clone();
+                    next_word.push(c);
+                    self.stack.push((next, next_word, next_states));
+                }
+            }
+            if dict.end && states.contains(&self.tokens.len()) {
+                return Some(word);
+            }
+        }
+        None
+    }
+}
+
+// test Pattern
+
+#[cfg(test)]
+mod test_pattern {
+    use super::*;
+
+    fn dict() -> Dict {
+        [
+            "a", "ab", "abs", "aback", "cat", "cot", "cut", "coat", "cats", "dates", "gates",
+            "mazes", "stab", "tab",
+        ]
+        .iter()
+        .copied()
+        .collect()
+    }
+
+    fn matches(pattern: &str) -> Vec<String> {
+        dict().matches(pattern).unwrap().collect()
+    }
+
+    #[test]
+    fn wildcards() {
+        assert_eq!(matches("c?t"), vec!["cat", "cot", "cut"]);
+        assert_eq!(matches("?a??s"), vec!["dates", "gates", "mazes"]);
+        assert_eq!(matches("ab*"), vec!["ab", "aback", "abs"]);
+        assert_eq!(matches("*ab"), vec!["ab", "stab", "tab"]);
+        assert_eq!(matches("**"), matches("*"));
+        assert_eq!(matches("*a*").len(), 12);
+        assert_eq!(matches("CAT"), vec!["cat"]);
+        assert!(matches("c?").is_empty());
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(matches("c[aeiou]t"), vec!["cat", "cot", "cut"]);
+        assert_eq!(matches("c[^a]t"), vec!["cot", "cut"]);
+        assert_eq!(
+            matches("[cs]*[st]"),
+            vec!["cat", "cats", "coat", "cot", "cut"]
+        );
+        assert_eq!(
+            dict().matches("c[ao").err(),
+            Some(PatternError::UnclosedClass { at: 1 })
+        );
+        assert_eq!(
+            dict().matches("c[]t").err(),
+            Some(PatternError::EmptyClass { at: 1 })
+        );
+    }
+
+    #[test]
+    fn lazy() {
+        let dict = dict();
+        let mut words = dict.matches("*").unwrap();
+
+        assert_eq!(words.next(), Some("a".to_string()));
+        assert_eq!(words.next(), Some("ab".to_string()));
+        assert_eq!(words.next(), Some("aback".to_string()));
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::BTreeSet as Set;
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use wordsearch::game::{self, Rejection};
+use wordsearch::lang::Language;
+use wordsearch::render::Render;
+use wordsearch::Board;
+
+// How many of the best missed words the summary lists
+const BEST_MISSED: usize = 10;
+
+// How many near misses a rejected guess suggests, and how close they must be
+const SUGGESTIONS: usize = 3;
+const SUGGESTION_DISTANCE: usize = 1;
+
+// Session
+
+/// One player's game on a board: the words found so far and their points.
+pub struct Session<'a> {
+    board: &'a Board,
+    language: &'a Language,
+    found: Vec<(String, u32)>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(board: &'a Board, language: &'a Language) -> Session<'a> {
+        Session {
+            board,
+            language,
+            found: vec![],
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        self.found.iter().map(|(_, points)| points).sum()
+    }
+
+    /// Judges a guess, recording it if it counts, and returns the reply to
+    /// show the player.
+    pub fn guess(&mut self, word: &str) -> String {
+        let word = game::normalize(word);
+        let judged = if self.found.iter().any(|(w, _)| *w == word) {
+            Err(Rejection::AlreadyFound)
+        } else {
+            game::judge(
+                self.board,
+                &self.language.dict,
+                &self.language.scores,
+                &word,
+            )
+        };
+
+        match judged {
+            Ok(_) => {
+                let points = self.language.scores.score_word(&word);
+                let reply = format!("{}: +{} (total {})", word, points, self.score() + points);
+                self.found.push((word, points));
+                reply
+            }
+            Err(rejection @ Rejection::NotOnBoard)
+            | Err(rejection @ Rejection::NotInDictionary) => {
+                let near = self.near_misses(&word);
+                if near.is_empty() {
+                    format!("{}: {}", word, rejection)
+                } else {
+                    format!(
+                        "{}: {} (did you mean {}?)",
+                        word,
+                        rejection,
+                        near.join(", ")
+                    )
+                }
+            }
+            Err(rejection) => format!("{}: {}", word, rejection),
+        }
+    }
+
+    // Scoring words on the board a letter away from `word`, not yet found.
+    fn near_misses(&self, word: &str) -> Vec<String> {
+        self.board
+            .near(&self.language.dict, word, SUGGESTION_DISTANCE)
+            .into_iter()
+            .map(|suggestion| suggestion.word)
+            .filter(|near| {
+                near != word
+                    && self.language.scores.score_word(near) > 0
+                    && !self.found.iter().any(|(w, _)| w == near)
+            })
+            .take(SUGGESTIONS)
+            .collect()
+    }
+
+    /// The final score, the words found, and the scoring words that were
+    /// missed, with the highest-value misses first.
+    pub fn summary(&self) -> String {
+        let found: Set<&str> = self.found.iter().map(|(w, _)| w.as_str()).collect();
+        let scores = &self.language.scores;
+        let mut missed: Vec<(String, u32)> = self
+            .board
+            .words(&self.language.dict)
+            .into_iter()
+            .filter(|word| scores.score_word(word) > 0 && !found.contains(word.as_str()))
+            .map(|word| {
+                let points = scores.score_word(&word);
+                (word, points)
+            })
+            .collect();
+        let possible: u32 = self.score() + missed.iter().map(|(_, points)| points).sum::<u32>();
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Score: {} of {} possible ({} of {} words)",
+            self.score(),
+            possible,
+            self.found.len(),
+            self.found.len() + missed.len()
+        );
+        let words: Vec<&str> = self.found.iter().map(|(w, _)| w.as_str()).collect();
+        let _ = writeln!(out, "Found: {}", words.join(" "));
+        let words: Vec<&str> = missed.iter().map(|(w, _)| w.as_str()).collect();
+        let _ = writeln!(out, "Missed: {}", words.join(" "));
+
+        missed.sort_by(|(a, p), (b, q)| q.cmp(p).then_with(|| a.cmp(b)));
+        let best: Vec<String> = missed
+            .iter()
+            .take(BEST_MISSED)
+            .map(|(w, points)| format!("{} ({})", w, points))
+            .collect();
+        let _ = writeln!(out, "Best missed: {}", best.join(", "));
+        out
+    }
+}
+
+/// Plays a timed game: shows the board, reads guesses a line at a time from
+/// `input` until `seconds` run out or input ends, then shows the summary.
+pub fn run<R, W>(board: &Board, language: &Language, seconds: u64, input: R, out: &mut W)
+where
+    R: BufRead + Send + 'static,
+    W: Write,
+{
+    // Lines are read on their own thread, so that the clock can end the game
+    // while the player is still typing.
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in input.lines() {
+            let sent = line.map(|line| sender.send(line).is_ok());
+            if sent.ok() != Some(true) {
+                break;
+            }
+        }
+    });
+
+    let mut session = Session::new(board, language);
+    let deadline = Instant::now() + Duration::from_secs(seconds);
+
+    let _ = write!(out, "{}", Render::default().board(board));
+    let _ = writeln!(
+        out,
+        "You have {} seconds. Enter one word per line.",
+        seconds
+    );
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            let _ = writeln!(out, "\nTime's up!");
+            break;
+        }
+        let _ = write!(out, "[{:>3}s] > ", left.as_secs() + 1);
+        let _ = out.flush();
+
+        match receiver.recv_timeout(left) {
+            Ok(line) if line.trim().is_empty() => {}
+            Ok(line) => {
+                let _ = writeln!(out, "{}", session.guess(&line));
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                let _ = writeln!(out);
+                break;
+            }
+        }
+    }
+    let _ = write!(out, "{}", session.summary());
+}
+
+// test play
+
+#[cfg(test)]
+mod test_play {
+    use super::*;
+    use std::io::Cursor;
+    use wordsearch::ParseOptions;
+
+    fn language() -> Language {
+        Language::new("test", vec!["an", "ant", "bat", "tab", "tan", "nab"])
+    }
+
+    #[test]
+    fn session() {
+        let board: Board = "ba tn".parse().unwrap();
+        let language = language();
+        let mut session = Session::new(&board, &language);
+
+        assert_eq!(session.guess("BAT"), "bat: +1 (total 1)");
+        assert_eq!(session.guess("bat"), "bat: already found");
+        assert_eq!(session.guess("an"), "an: too short, words need 3 letters");
+        assert_eq!(
+            session.guess("tabs"),
+            "tabs: not on the board (did you mean tab?)"
+        );
+        assert_eq!(
+            session.guess("nat"),
+            "nat: not in the dictionary (did you mean nab?)"
+        );
+        assert_eq!(session.guess("zzz"), "zzz: not on the board");
+        assert_eq!(session.guess("tan"), "tan: +1 (total 2)");
+        assert_eq!(
+            session.summary(),
+            "Score: 2 of 5 possible (2 of 5 words)\n\
+             Found: bat tan\n\
+             Missed: ant nab tab\n\
+             Best missed: ant (1), nab (1), tab (1)\n"
+        );
+    }
+
+    #[test]
+    fn qu_tile() {
+        let options = ParseOptions {
+            qu_tiles: true,
+            ..ParseOptions::default()
+        };
+        let board = Board::parse_with("qit abp", &options).unwrap();
+        let language = Language::new("test", vec!["quit", "quip", "tip", "qat"]);
+        let mut session = Session::new(&board, &language);
+
+        assert_eq!(session.guess("quit"), "quit: +1 (total 1)");
+        assert_eq!(session.guess("qat"), "qat: not on the board");
+        assert_eq!(
+            session.guess("quips"),
+            "quips: not on the board (did you mean quip?)"
+        );
+        assert!(session.summary().starts_with("Score: 1 of 3 possible"));
+    }
+
+    #[test]
+    fn run_until_input_ends() {
+        let board: Board = "ba tn".parse().unwrap();
+        let mut out = vec![];
+
+        run(
+            &board,
+            &language(),
+            60,
+            Cursor::new("tab\n\nant\n"),
+            &mut out,
+        );
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("B A\nT N\nYou have 60 seconds."));
+        assert!(out.contains("tab: +1 (total 1)\n"));
+        assert!(out.contains("ant: +1 (total 2)\n"));
+        assert!(out.contains("Score: 2 of 5 possible"));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{fold_case, text, Dict};
+use std::collections::BTreeMap as Map;
+
+// Rack
+
+/// A multiset of letters to make words from, Scrabble style, plus blanks
+/// that can stand for any letter.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Rack {
+    letters: Map<char, u32>,
+    blanks: u32,
+}
+
+impl Rack {
+    /// Reads a rack written as its letters, with `?` or `_` for a blank.
+    /// Whitespace is skipped and letters are lowercased, as in `Dict`.
+    pub fn parse(s: &str) -> Rack {
+        let mut rack = Rack::default();
+        for c in text::compose_latin(s)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+        {
+            match c {
+                '?' | '_' => rack.blanks += 1,
+                c => *rack.letters.entry(fold_case(c)).or_default() += 1,
+            }
+        }
+        rack
+    }
+
+    pub fn len(&self) -> usize {
+        (self.letters.values().sum::<u32>() + self.blanks) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every word in `dict` that can be made from the rack, in alphabetical
+    /// order. A blank is only used where the rack has run out of the letter,
+    /// so each word is listed once, with as few blanks as it needs.
+    pub fn words(&self, dict: &Dict) -> Vec<Play> {
+        let mut plays = vec![];
+        let mut letters = self.letters.clone();
+        let mut play = Play::default();
+        walk(dict, &mut letters, self.blanks, &mut play, &mut plays);
+        plays
+    }
+}
+
+fn walk(
+    dict: &Dict,
+    letters: &mut Map<char, u32>,
+    blanks: u32,
+    play: &mut Play,
+    plays: &mut Vec<Play>,
+) {
+    for (&c, next) in dict.next.iter() {
+        let blanks = match letters.get_mut(&c) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                blanks
+            }
+            _ if blanks > 0 => {
+                play.blanks.push(play.word.chars().count());
+                blanks - 1
+            }
+            _ => continue,
+        };
+        play.word.push(c);
+
+        if next.end {
+            plays.push(play.clone());
+        }
+        walk(next, letters, blanks, play, plays);
+
+        let blank = play.blanks.last() == Some(&(play.word.chars().count() - 1));
+        play.word.pop();
+        if blank {
+            play.blanks.pop();
+        } else {
+            *letters.get_mut(&c).unwrap() += 1;
+        }
+    }
+}
+
+/// A word made from a rack, with the positions of the letters, counting
+/// from 0, that blanks stand for.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Play {
+    pub word: String,
+    pub blanks: Vec<usize>,
+}
+
+// LetterValues
+
+/// Points for each letter of a word. Blanks, and letters not in the table,
+/// are worth nothing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LetterValues {
+    values: Map<char, u32>,
+}
+
+impl LetterValues {
+    pub fn new(values: Map<char, u32>) -> LetterValues {
+        LetterValues { values }
+    }
+
+    /// The English Scrabble letter values.
+    pub fn scrabble() -> LetterValues {
+        let table = [
+            (1, "aeilnorstu"),
+            (2, "dg"),
+            (3, "bcmp"),
+            (4, "fhvwy"),
+            (5, "k"),
+            (8, "jx"),
+            (10, "qz"),
+        ];
+        LetterValues::new(
+            table
+                .iter()
+                .flat_map(|&(value, letters)| letters.chars().map(move |c| (c, value)))
+                .collect(),
+        )
+    }
+
+    /// Reads lines of `letter value`, returning the number of the first bad
+    /// line, counting from 1.
+    pub fn parse(s: &str) -> Result<LetterValues, usize> {
+        let mut values = Map::new();
+        for (i, line) in s.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [] => {}
+                [letter, value] => {
+                    let mut chars = letter.chars().map(fold_case);
+                    match (chars.next(), chars.next(), value.parse()) {
+                        (Some(c), None, Ok(value)) => {
+                            values.insert(c, value);
+                        }
+                        _ => return Err(i + 1),
+                    }
+                }
+                _ => return Err(i + 1),
+            }
+        }
+        Ok(LetterValues::new(values))
+    }
+
+    pub fn value(&self, c: char) -> u32 {
+        self.values.get(&c).copied().unwrap_or(0)
+    }
+
+    pub fn score(&self, play: &Play) -> u32 {
+        play.word
+            .chars()
+            .enumerate()
+            .filter(|(i, _)| !play.blanks.contains(i))
+            .map(|(_, c)| self.value(c))
+            .sum()
+    }
+}
+
+// RackOrder
+
+/// How to order plays: alphabetically, or longest or highest valued first
+/// with ties in alphabetical order.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RackOrder {
+    Alpha,
+    Length,
+    Value(LetterValues),
+}
+
+impl RackOrder {
+    pub fn sort(&self, plays: &mut [Play]) {
+        match self {
+            RackOrder::Alpha => plays.sort_by(|a, b| a.word.cmp(&b.word)),
+            RackOrder::Length => plays.sort_by(|a, b| {
+                let length = |p: &Play| p.word.chars().count();
+                length(b).cmp(&length(a)).then_with(|| a.word.cmp(&b.word))
+            }),
+            RackOrder::Value(values) => plays.sort_by(|a, b| {
+                values
+                    .score(b)
+                    .cmp(&values.score(a))
+                    .then_with(|| a.word.cmp(&b.word))
+            }),
+        }
+    }
+}
+
+// test Rack
+
+#[cfg(test)]
+mod test_rack {
+    use super::*;
+
+    fn words(plays: &[Play]) -> Vec<&str> {
+        plays.iter().map(|p| p.word.as_str()).collect()
+    }
+
+    #[test]
+    fn parse() {
+        let rack = Rack::parse("Ta b?_a");
+
+        assert_eq!(rack.len(), 6);
+        assert_eq!(rack.blanks, 2);
+        assert_eq!(
+            rack.letters,
+            vec![('a', 2), ('b', 1), ('t', 1)].into_iter().collect()
+        );
+        assert!(Rack::parse(" ").is_empty());
+    }
+
+    #[test]
+    fn rack_words() {
+        let dict: Dict = ["a", "at", "bat", "tab", "tabs", "batt", "abbot", "cat"]
+            .iter()
+            .copied()
+            .collect();
+
+        assert_eq!(
+            words(&Rack::parse("tab").words(&dict)),
+            vec!["a", "at", "bat", "tab"]
+        );
+        assert_eq!(
+            Rack::parse("ta?").words(&dict),
+            vec![
+                Play {
+                    word: "a".to_string(),
+                    blanks: vec![]
+                },
+                Play {
+                    word: "at".to_string(),
+                    blanks: vec![]
+                },
+                Play {
+                    word: "bat".to_string(),
+                    blanks: vec![0]
+                },
+                Play {
+                    word: "cat".to_string(),
+                    blanks: vec![0]
+                },
+                Play {
+                    word: "tab".to_string(),
+                    blanks: vec![2]
+                },
+            ]
+        );
+        assert_eq!(
+            words(&Rack::parse("tb??").words(&dict)),
+            vec!["a", "at", "bat", "batt", "cat", "tab", "tabs"]
+        );
+        assert!(Rack::parse("").words(&dict).is_empty());
+    }
+
+    #[test]
+    fn order() {
+        let dict: Dict = ["ax", "tax", "taxa", "zoa"].iter().copied().collect();
+        let mut plays = Rack::parse("taxa??").words(&dict);
+
+        RackOrder::Length.sort(&mut plays);
+        assert_eq!(words(&plays), vec!["taxa", "tax", "zoa", "ax"]);
+
+        RackOrder::Value(LetterValues::scrabble()).sort(&mut plays);
+        assert_eq!(words(&plays), vec!["taxa", "tax", "ax", "zoa"]);
+
+        RackOrder::Alpha.sort(&mut plays);
+        assert_eq!(words(&plays), vec!["ax", "tax", "taxa", "zoa"]);
+    }
+
+    #[test]
+    fn letter_values() {
+        let values = LetterValues::parse("a 1\n\nQ 10\n").unwrap();
+
+        assert_eq!(values.value('q'), 10);
+        assert_eq!(values.value('z'), 0);
+        assert_eq!(
+            values.score(&Play {
+                word: "aqa".to_string(),
+                blanks: vec![1]
+            }),
+            2
+        );
+        assert_eq!(LetterValues::parse("a 1\nab 2\n"), Err(2));
+        assert_eq!(LetterValues::scrabble().value('k'), 5);
+    }
+}
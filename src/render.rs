@@ -0,0 +1,130 @@
+use crate::{Board, Path, QU_TILE};
+use std::fmt;
+
+const BOLD_YELLOW: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+// Render
+
+/// Draws a board as aligned rows of tiles, optionally highlighting a path.
+///
+/// Tiles are uppercased, and drawn as the "Qu" tile where the board reads `q`
+/// cells as one (see `Board::qu_tiles`), so each tile shows what it spells.
+/// Void cells are drawn as `.`. On a highlighted path each tile is followed by
+/// its step number, counting from 1, and with `color` set both are drawn in
+/// bold yellow.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Render {
+    pub color: bool,
+}
+
+impl Render {
+    /// The text drawn for the letter `c` on `board`.
+    pub fn tile(&self, board: &Board, c: char) -> String {
+        if board.qu_tiles() && c == QU_TILE {
+            "Qu".to_string()
+        } else {
+            c.to_uppercase().collect()
+        }
+    }
+
+    pub fn board(&self, board: &Board) -> String {
+        self.path(board, &vec![])
+    }
+
+    pub fn path(&self, board: &Board, path: &Path) -> String {
+        let tiles: Vec<Option<String>> = (0..board.width() * board.height())
+            .map(|pos| board.char_at(pos).map(|c| self.tile(board, c)))
+            .collect();
+        let tile_width = tiles
+            .iter()
+            .flatten()
+            .map(|tile| tile.chars().count())
+            .max()
+            .unwrap_or(1);
+        let step_width = if path.is_empty() {
+            0
+        } else {
+            path.len().to_string().len()
+        };
+
+        let cell = |pos: u32| {
+            let tile = tiles[pos as usize]
+                .clone()
+                .unwrap_or_else(|| ".".to_string());
+            let width = tile_width + step_width;
+            match path.iter().position(|&p| p == pos) {
+                Some(step) => {
+                    let text = format!("{}{}", tile, step + 1);
+                    let padding = " ".repeat(width.saturating_sub(text.chars().count()));
+                    if self.color {
+                        format!("{}{}{}{}", BOLD_YELLOW, text, RESET, padding)
+                    } else {
+                        text + &padding
+                    }
+                }
+                None => format!("{:<width$}", tile, width = width),
+            }
+        };
+
+        let mut out = String::new();
+        for row in 0..board.height() {
+            let cells: Vec<String> = (0..board.width())
+                .map(|col| cell(row * board.width() + col))
+                .collect();
+            out.push_str(cells.join(" ").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&Render::default().board(self))
+    }
+}
+
+// test Render
+
+#[cfg(test)]
+mod test_render {
+    use super::*;
+    use crate::{NonLetters, ParseOptions};
+
+    #[test]
+    fn display() {
+        let board: Board = "qat bin".parse().unwrap();
+        assert_eq!(board.to_string(), "Q A T\nB I N\n");
+
+        let options = ParseOptions {
+            qu_tiles: true,
+            ..ParseOptions::default()
+        };
+        let board = Board::parse_with("qat bin", &options).unwrap();
+        assert_eq!(board.to_string(), "Qu A  T\nB  I  N\n");
+    }
+
+    #[test]
+    fn path() {
+        let board: Board = "bat tan".parse().unwrap();
+        let render = Render::default();
+
+        assert_eq!(render.path(&board, &vec![0, 1, 2]), "B1 A2 T3\nT  A  N\n");
+        assert_eq!(
+            Render { color: true }.path(&board, &vec![4, 5]),
+            "B  A  T\nT  \u{1b}[1;33mA1\u{1b}[0m \u{1b}[1;33mN2\u{1b}[0m\n"
+        );
+    }
+
+    #[test]
+    fn void() {
+        let options = ParseOptions {
+            non_letters: NonLetters::Void,
+            ..ParseOptions::default()
+        };
+        let board = Board::parse_with("ab.d ef-h", &options).unwrap();
+
+        assert_eq!(board.to_string(), "A B . D\nE F . H\n");
+    }
+}
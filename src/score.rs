@@ -0,0 +1,81 @@
+// ScoreTable
+
+/// Points awarded for a word by its length in cells.
+///
+/// `points[n]` is the value of an `n`-letter word; words longer than the table
+/// score its last entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreTable {
+    points: Vec<u32>,
+}
+
+impl ScoreTable {
+    pub fn new(points: Vec<u32>) -> ScoreTable {
+        ScoreTable { points }
+    }
+
+    /// The classic Boggle table: 3-4 letters score 1, 5 score 2, 6 score 3,
+    /// 7 score 5, and 8 or more score 11.
+    pub fn boggle() -> ScoreTable {
+        ScoreTable::new(vec![0, 0, 0, 1, 1, 2, 3, 5, 11])
+    }
+
+    /// The length of the shortest word worth any points.
+    pub fn min_length(&self) -> usize {
+        self.points.iter().position(|&p| p > 0).unwrap_or(0)
+    }
+
+    pub fn score_word(&self, word: &str) -> u32 {
+        self.score_length(word.chars().count())
+    }
+
+    pub fn score_length(&self, length: usize) -> u32 {
+        match self.points.get(length) {
+            Some(&points) => points,
+            None => self.points.last().copied().unwrap_or(0),
+        }
+    }
+
+    pub fn score_words<'a, T>(&self, words: T) -> u32
+    where
+        T: IntoIterator<Item = &'a str>,
+    {
+        words.into_iter().map(|word| self.score_word(word)).sum()
+    }
+}
+
+impl Default for ScoreTable {
+    fn default() -> Self {
+        ScoreTable::boggle()
+    }
+}
+
+// test ScoreTable
+
+#[cfg(test)]
+mod test_score {
+    use super::*;
+
+    #[test]
+    fn boggle() {
+        let table = ScoreTable::boggle();
+
+        let scores: Vec<u32> = [
+            "an",
+            "ant",
+            "anti",
+            "bantu",
+            "banana",
+            "bananas",
+            "bandanas",
+            "abandoning",
+        ]
+        .iter()
+        .map(|word| table.score_word(word))
+        .collect();
+
+        assert_eq!(scores, vec![0, 1, 1, 2, 3, 5, 11, 11]);
+        assert_eq!(table.min_length(), 3);
+        assert_eq!(table.score_words(vec!["ant", "bantu"]), 3);
+    }
+}
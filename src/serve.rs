@@ -0,0 +1,518 @@
+use crate::output::{self, json_string};
+use std::collections::BTreeMap as Map;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wordsearch::game;
+use wordsearch::lang::Language;
+use wordsearch::rng::Rng;
+use wordsearch::{Board, Cell, ParseOptions, Path};
+
+// Response
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Response {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            _ => "Error",
+        }
+    }
+}
+
+// Routes
+
+/// Answers one request. Every endpoint is a GET with its arguments in the
+/// query string, and replies with JSON:
+///
+/// - `/solve?board=` lists the words, as `solve --format json` does
+/// - `/check?board=&word=` checks a guess and gives its path
+/// - `/validate?board=&path=` checks a traced path of comma-separated
+///   `row:col` cells, or of cell numbers
+/// - `/generate?seed=&width=` rolls a board from the language's dice
+pub fn route(method: &str, target: &str, language: &Language) -> Response {
+    if method != "GET" {
+        return Response::error(405, "only GET is supported");
+    }
+    let (path, query) = match target.find('?') {
+        Some(i) => (&target[..i], parse_query(&target[i + 1..])),
+        None => (target, Map::new()),
+    };
+
+    let board = || -> Result<Board, Response> {
+        let board = query
+            .get("board")
+            .ok_or_else(|| Response::error(400, "missing board"))?;
+        let options = ParseOptions {
+            qu_tiles: query.get("qu").is_some_and(|qu| qu == "1"),
+            ..ParseOptions::default()
+        };
+        let board = Board::parse_with(board, &options)
+            .map_err(|error| Response::error(400, &error.to_string()))?;
+        language
+            .check_board(&board)
+            .map_err(|error| Response::error(400, &error.to_string()))?;
+        Ok(board)
+    };
+
+    let result = match path {
+        "/solve" => board().map(|board| {
+            let paths = board.search(&language.dict);
+            let found = output::found_words(&board, &paths, &language.scores);
+            Response::ok(output::json(&board, &found))
+        }),
+        "/check" => board().and_then(|board| {
+            let word = query
+                .get("word")
+                .ok_or_else(|| Response::error(400, "missing word"))?;
+            let word = game::normalize(word);
+            let body = match game::judge(&board, &language.dict, &language.scores, &word) {
+                Ok(path) => {
+                    let found = output::Found {
+                        score: language.scores.score_word(&word),
+                        word,
+                        path,
+                    };
+                    format!(
+                        "{{\"ok\":true,\"found\":{}}}",
+                        output::json_record(&board, &found)
+                    )
+                }
+                Err(rejection) => format!(
+                    "{{\"ok\":false,\"word\":{},\"reason\":{}}}",
+                    json_string(&word),
+                    json_string(&rejection.to_string())
+                ),
+            };
+            Ok(Response::ok(body))
+        }),
+        "/validate" => board().and_then(|board| {
+            let cells: Vec<Result<u32, Cell>> = query
+                .get("path")
+                .ok_or_else(|| Response::error(400, "missing path"))?
+                .split(',')
+                .map(|cell| parse_cell(&board, cell.trim()))
+                .collect::<Option<_>>()
+                .ok_or_else(|| {
+                    Response::error(400, "path must be comma-separated row:col cells")
+                })?;
+            let path: Path = cells.iter().map_while(|cell| cell.ok()).collect();
+            let validated = match cells.get(path.len()) {
+                // a row:col cell off the board fails like a numbered one would,
+                // once the steps before it check out
+                Some(&Err(cell)) => board
+                    .validate_path(&path)
+                    .map_err(|error| error.to_string())
+                    .and_then(|_| {
+                        Err(format!(
+                            "step {}: cell {} is not on the board",
+                            path.len(),
+                            cell
+                        ))
+                    }),
+                _ => board
+                    .validate_word(&path, &language.dict)
+                    .map_err(|error| error.to_string()),
+            };
+            let body = match validated {
+                Ok(word) => format!(
+                    "{{\"ok\":true,\"word\":{},\"score\":{}}}",
+                    json_string(&word),
+                    language.scores.score_word(&word)
+                ),
+                Err(reason) => format!("{{\"ok\":false,\"reason\":{}}}", json_string(&reason)),
+            };
+            Ok(Response::ok(body))
+        }),
+        "/generate" => {
+            let dice = match &language.dice {
+                Some(dice) => dice,
+                None => return Response::error(400, "language has no dice"),
+            };
+            let number = |name: &str| -> Result<Option<u64>, Response> {
+                query
+                    .get(name)
+                    .map(|n| {
+                        n.parse()
+                            .map_err(|_| Response::error(400, &format!("invalid {}", name)))
+                    })
+                    .transpose()
+            };
+            number("seed").and_then(|seed| {
+                let width = number("width")?
+                    .map(|w| w as usize)
+                    .or_else(|| dice.square_width())
+                    .ok_or_else(|| Response::error(400, "missing width"))?;
+                let seed = seed.unwrap_or_else(|| {
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|time| time.as_nanos() as u64)
+                        .unwrap_or(0)
+                });
+                let board = dice.roll(&mut Rng::new(seed), width);
+                let parsed = Board::parse_with(&board, &ParseOptions::rolled())
+                    .map_err(|error| Response::error(400, &error.to_string()))?;
+                language
+                    .check_board(&parsed)
+                    .map_err(|error| Response::error(400, &error.to_string()))?;
+                Ok(Response::ok(format!(
+                    "{{\"board\":{},\"seed\":{}}}",
+                    json_string(&board),
+                    seed
+                )))
+            })
+        }
+        _ => Err(Response::error(404, "not found")),
+    };
+    result.unwrap_or_else(|response| response)
+}
+
+// A `row:col` cell, or a bare cell number. A `row:col` cell off the board
+// has no number, and is given back as it is.
+fn parse_cell(board: &Board, cell: &str) -> Option<Result<u32, Cell>> {
+    if cell.contains(':') {
+        let cell: Cell = cell.parse().ok()?;
+        Some(board.pos(cell).ok_or(cell))
+    } else {
+        cell.parse().ok().map(Ok)
+    }
+}
+
+fn parse_query(query: &str) -> Map<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.find('=') {
+            Some(i) => (percent_decode(&pair[..i]), percent_decode(&pair[i + 1..])),
+            None => (percent_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' if i + 2 < bytes.len() => std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                out.push(byte);
+                i += 3;
+            }
+            (None, b'+') => {
+                out.push(b' ');
+                i += 1;
+            }
+            (None, byte) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// Server
+
+/// Answers requests on `listener` until it fails, one thread per connection,
+/// sharing the loaded language between them.
+// Limits, so that slow or oversized requests can't tie the server up.
+const TIMEOUT: Duration = Duration::from_secs(10);
+// bytes of request line and headers read before answering 431
+const MAX_HEAD: u64 = 8192;
+// connections handled at once; more are answered 503 straight away
+const MAX_CONNECTIONS: usize = 64;
+
+pub fn serve(listener: TcpListener, language: Arc<Language>) {
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    let busy = Response::error(503, "too many connections");
+                    if let Err(error) = respond(stream, &busy) {
+                        eprintln!("Connection error: {}", error);
+                    }
+                    continue;
+                }
+                let language = Arc::clone(&language);
+                let active = Arc::clone(&active);
+                thread::spawn(move || {
+                    if let Err(error) = handle(stream, &language) {
+                        eprintln!("Connection error: {}", error);
+                    }
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+            Err(error) => eprintln!("Connection error: {}", error),
+        }
+    }
+}
+
+// Reads one request and writes its response; connections are not kept alive.
+fn handle(stream: TcpStream, language: &Language) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_HEAD));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut ended = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break;
+        }
+        if header.trim().is_empty() {
+            ended = true;
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = if !ended && reader.get_ref().limit() == 0 {
+        Response::error(431, "request head too large")
+    } else {
+        match (parts.next(), parts.next()) {
+            (Some(method), Some(target)) => route(method, target, language),
+            _ => Response::error(400, "malformed request"),
+        }
+    };
+    respond(stream, &response)
+}
+
+fn respond(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+// test serve
+
+#[cfg(test)]
+mod test_serve {
+    use super::*;
+    use std::io::Read;
+    use wordsearch::dice::Dice;
+
+    fn language() -> Language {
+        Language {
+            dice: Some(Dice::parse("abnt abnt abnt abnt")),
+            ..Language::new("test", vec!["an", "ant", "bat", "tab"])
+        }
+    }
+
+    #[test]
+    fn routes() {
+        let language = language();
+
+        assert_eq!(
+            route("GET", "/solve?board=ba+tn", &language),
+            Response::ok(
+                "[{\"word\":\"an\",\"length\":2,\"score\":0,\"path\":[{\"row\":0,\"col\":1},{\"row\":1,\"col\":1}]},\
+                 {\"word\":\"ant\",\"length\":3,\"score\":1,\"path\":[{\"row\":0,\"col\":1},{\"row\":1,\"col\":1},{\"row\":1,\"col\":0}]},\
+                 {\"word\":\"bat\",\"length\":3,\"score\":1,\"path\":[{\"row\":0,\"col\":0},{\"row\":0,\"col\":1},{\"row\":1,\"col\":0}]},\
+                 {\"word\":\"tab\",\"length\":3,\"score\":1,\"path\":[{\"row\":1,\"col\":0},{\"row\":0,\"col\":1},{\"row\":0,\"col\":0}]}]"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            route("GET", "/check?board=ba%20tn&word=TAB", &language).body,
+            "{\"ok\":true,\"found\":{\"word\":\"tab\",\"length\":3,\"score\":1,\"path\":[{\"row\":1,\"col\":0},{\"row\":0,\"col\":1},{\"row\":0,\"col\":0}]}}"
+        );
+        assert_eq!(
+            route("GET", "/check?board=ba+tn&word=nab", &language).body,
+            "{\"ok\":false,\"word\":\"nab\",\"reason\":\"not in the dictionary\"}"
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0,1,2", &language).body,
+            "{\"ok\":true,\"word\":\"bat\",\"score\":1}"
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0:0,0:1,1:0", &language).body,
+            "{\"ok\":true,\"word\":\"bat\",\"score\":1}"
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0:0,0:2", &language),
+            Response::ok(
+                "{\"ok\":false,\"reason\":\"step 1: cell 0:2 is not on the board\"}".to_string()
+            )
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0,6", &language),
+            Response::ok(
+                "{\"ok\":false,\"reason\":\"step 1: cell 6 is not on the board\"}".to_string()
+            )
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0:0,0:0,5:5", &language).body,
+            "{\"ok\":false,\"reason\":\"step 1: cell 0 is already used\"}"
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0,1,0", &language).body,
+            "{\"ok\":false,\"reason\":\"step 2: cell 0 is already used\"}"
+        );
+        assert_eq!(
+            route("GET", "/generate?seed=4", &language),
+            route("GET", "/generate?seed=4", &language)
+        );
+        assert!(route("GET", "/generate?seed=4", &language)
+            .body
+            .ends_with(",\"seed\":4}"));
+    }
+
+    #[test]
+    fn qu_tiles() {
+        let language = Language::new("test", vec!["qat", "quit"]);
+        let ok = |target: &str| {
+            route("GET", target, &language)
+                .body
+                .starts_with("{\"ok\":true")
+        };
+
+        assert!(ok("/check?board=qa+ti&word=qat"));
+        assert!(!ok("/check?board=qi+ta&word=quit"));
+        assert!(ok("/check?board=qi+ta&word=quit&qu=1"));
+        assert!(!ok("/check?board=qa+ti&word=qat&qu=1"));
+    }
+
+    #[test]
+    fn route_errors() {
+        let language = language();
+
+        assert_eq!(route("GET", "/nope", &language).status, 404);
+        assert_eq!(route("POST", "/solve", &language).status, 405);
+        assert_eq!(
+            route("GET", "/solve", &language),
+            Response::error(400, "missing board")
+        );
+        assert_eq!(
+            route("GET", "/solve?board=abc", &language),
+            Response::error(400, "must have at least two rows, found 1")
+        );
+        assert_eq!(
+            route("GET", "/solve?board=ab+tx", &language),
+            Response::error(400, "'x' at 1:1 is not in the test alphabet")
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0,x", &language).status,
+            400
+        );
+        assert_eq!(
+            route("GET", "/validate?board=ba+tn&path=0:0,0:x", &language),
+            Response::error(400, "path must be comma-separated row:col cells")
+        );
+        assert_eq!(route("GET", "/generate?seed=x", &language).status, 400);
+        assert_eq!(
+            route("GET", "/generate?seed=1&width=0", &language),
+            Response::error(400, "must have at least two rows, found 0")
+        );
+        assert_eq!(
+            route("GET", "/generate?seed=1&width=99", &language).status,
+            400
+        );
+
+        let no_dice = Language::new("test", vec!["an", "ant"]);
+        assert_eq!(
+            route("GET", "/generate?seed=1", &no_dice),
+            Response::error(400, "language has no dice")
+        );
+        let foreign_dice = Language {
+            dice: Some(Dice::parse("xx xx xx xx")),
+            ..language
+        };
+        assert_eq!(
+            route("GET", "/generate?seed=1", &foreign_dice),
+            Response::error(400, "'x' at 0:0 is not in the test alphabet")
+        );
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(percent_decode("a+b%20c%C3%A9%2"), "a b cé%2");
+        assert_eq!(
+            parse_query("board=ab+cd&word=%C3%A9t%C3%A9&flag"),
+            vec![
+                ("board".to_string(), "ab cd".to_string()),
+                ("flag".to_string(), String::new()),
+                ("word".to_string(), "été".to_string()),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn serve_localhost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let language = Arc::new(language());
+        thread::spawn(move || serve(listener, language));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "GET /check?board=ba+tn&word=bat HTTP/1.1\r\nHost: localhost\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json\r\n"));
+        assert!(response.ends_with("\r\n\r\n{\"ok\":true,\"found\":{\"word\":\"bat\",\"length\":3,\"score\":1,\"path\":[{\"row\":0,\"col\":0},{\"row\":0,\"col\":1},{\"row\":1,\"col\":0}]}}"));
+    }
+
+    #[test]
+    fn serve_head_too_large() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let language = Arc::new(language());
+        thread::spawn(move || serve(listener, language));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        let target = "a".repeat(MAX_HEAD as usize - "GET /".len());
+        write!(stream, "GET /{}", target).unwrap();
+        stream.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+    }
+}
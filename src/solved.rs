@@ -0,0 +1,242 @@
+use crate::{Board, Dict, Path};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap as Map;
+use std::collections::BTreeSet as Set;
+use std::collections::VecDeque;
+
+// SolvedBoard
+
+/// A board together with its search results, kept up to date as cells change.
+///
+/// Changing a cell only drops the paths that pass through it and searches for
+/// the new ones that do, pruning any branch that can't reach the cell before
+/// the dictionary runs out of letters. The results always match a full
+/// `Board::search`, in sorted order.
+pub struct SolvedBoard<'a> {
+    board: Board,
+    dict: &'a Dict,
+    paths: Set<Path>,
+    // the number of paths that spell each word
+    words: Map<String, usize>,
+}
+
+impl<'a> SolvedBoard<'a> {
+    pub fn new(board: Board, dict: &'a Dict) -> SolvedBoard<'a> {
+        let mut solved = SolvedBoard {
+            board,
+            dict,
+            paths: Set::new(),
+            words: Map::new(),
+        };
+        for path in solved.board.search(dict) {
+            solved.insert(path);
+        }
+        solved
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Every path that spells a word, sorted.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.paths.iter()
+    }
+
+    /// The distinct words, sorted.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.keys().map(String::as_str)
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Changes the letter at `pos` and updates the results, returning the old
+    /// letter, or `None` if there is no such cell.
+    pub fn set_char(&mut self, pos: u32, c: char) -> Option<char> {
+        let old = self.board.char_at(pos)?;
+        if old == c {
+            return Some(old);
+        }
+
+        // stale paths are spelled with the old letter, so drop them first
+        let stale: Vec<Path> = self
+            .paths
+            .iter()
+            .filter(|path| path.contains(&pos))
+            .cloned()
+            .collect();
+        for path in stale {
+            self.remove(&path);
+        }
+        self.board.set_char(pos, c);
+
+        let distance = self.distances(pos);
+        let mut found = vec![];
+        let mut path = vec![];
+        for &start in self.board.grid.0.keys() {
+            self.search_through(self.dict, &mut path, start, pos, &distance, &mut found);
+        }
+        for path in found {
+            self.insert(path);
+        }
+
+        Some(old)
+    }
+
+    fn insert(&mut self, path: Path) {
+        let word = self.board.path_to_word(&path);
+        if self.paths.insert(path) {
+            *self.words.entry(word).or_default() += 1;
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if self.paths.remove(path) {
+            let word = self.board.path_to_word(path);
+            if let Some(count) = self.words.get_mut(&word) {
+                *count -= 1;
+                if *count == 0 {
+                    self.words.remove(&word);
+                }
+            }
+        }
+    }
+
+    // Steps from every cell to `target`, by breadth-first search.
+    fn distances(&self, target: u32) -> Map<u32, usize> {
+        let mut distance = Map::new();
+        let mut queue = VecDeque::new();
+        distance.insert(target, 0);
+        queue.push_back(target);
+        while let Some(pos) = queue.pop_front() {
+            let d = distance[&pos];
+            for &next in self.board.grid.0.get(&pos).into_iter().flatten() {
+                if let Entry::Vacant(entry) = distance.entry(next) {
+                    entry.insert(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distance
+    }
+
+    // Like `Board::search_step`, but only keeps paths through `target`, and
+    // gives up on a branch once `target` is further away than the longest
+    // word the dictionary has left.
+    fn search_through(
+        &self,
+        dict: &Dict,
+        path: &mut Path,
+        pos: u32,
+        target: u32,
+        distance: &Map<u32, usize>,
+        results: &mut Vec<Path>,
+    ) {
+        let qu_tiles = self.board.qu_tiles;
+        let dict_here = match self
+            .board
+            .chars
+            .get(&pos)
+            .and_then(|&c| dict.next_tile(c, qu_tiles))
+        {
+            Some(dict_here) => dict_here,
+            None => return,
+        };
+        let through = pos == target || path.contains(&target);
+        if !through {
+            match distance.get(&pos) {
+                // reaching target takes `d` more letters after this one
+                Some(&d) if d <= dict_here.height => {}
+                _ => return,
+            }
+        }
+
+        path.push(pos);
+        for &next in self.board.grid.0[&pos].iter() {
+            if !path.contains(&next) {
+                self.search_through(dict_here, path, next, target, distance, results);
+            }
+        }
+        if dict_here.end && through {
+            results.push(path.clone());
+        }
+        path.pop();
+    }
+}
+
+// test SolvedBoard
+
+#[cfg(test)]
+mod test_solved {
+    use super::*;
+    use crate::rng::Rng;
+    use crate::ParseOptions;
+
+    fn full(board: &Board, dict: &Dict) -> Vec<Path> {
+        let mut paths = board.search(dict);
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn set_char() {
+        let dict: Dict = ["an", "ant", "bat", "tab", "tan", "nab", "cat", "act"]
+            .iter()
+            .copied()
+            .collect();
+        let board: Board = "ba tn".parse().unwrap();
+        let mut solved = SolvedBoard::new(board, &dict);
+
+        assert_eq!(
+            solved.words().collect::<Vec<_>>(),
+            vec!["an", "ant", "bat", "nab", "tab", "tan"]
+        );
+        assert_eq!(solved.set_char(0, 'c'), Some('b'));
+        assert_eq!(
+            solved.words().collect::<Vec<_>>(),
+            vec!["act", "an", "ant", "cat", "tan"]
+        );
+        assert_eq!(
+            solved.paths().cloned().collect::<Vec<_>>(),
+            full(solved.board(), &dict)
+        );
+        assert_eq!(solved.set_char(9, 'x'), None);
+    }
+
+    #[test]
+    fn matches_full_search() {
+        let words = [
+            "an", "and", "ant", "ants", "anti", "bad", "bade", "bat", "bats", "bean", "beat",
+            "den", "dent", "eat", "east", "neat", "net", "nets", "sand", "sane", "sat", "seat",
+            "set", "stab", "stand", "tab", "tabs", "tan", "tans", "tea", "ten", "tend", "quad",
+            "quest", "quiet", "squat", "quint",
+        ];
+        let dict: Dict = words.iter().copied().collect();
+
+        for qu_tiles in [false, true] {
+            let options = ParseOptions {
+                qu_tiles,
+                ..ParseOptions::default()
+            };
+            let board = Board::parse_with("bant dest nabe", &options).unwrap();
+            let mut solved = SolvedBoard::new(board, &dict);
+            let mut rng = Rng::new(11);
+
+            for _ in 0..200 {
+                let pos = rng.below(12) as u32;
+                let c = *rng
+                    .choose(&['a', 'b', 'd', 'e', 'i', 'n', 'q', 's', 't', 'u'])
+                    .unwrap();
+                solved.set_char(pos, c);
+
+                assert_eq!(
+                    solved.paths().cloned().collect::<Vec<_>>(),
+                    full(solved.board(), &dict)
+                );
+                assert_eq!(solved.word_count(), solved.board().words(&dict).len());
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::render::Render;
+use crate::{Board, Path};
+use std::fmt::Write;
+
+const PALETTE: [&str; 6] = [
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4",
+];
+
+// Svg
+
+/// Draws a board as an SVG image of letter tiles, with paths overlaid as
+/// arrowed polylines through the cell centers. Each path gets the next color
+/// of a small palette.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svg {
+    pub cell_size: u32,
+    pub render: Render,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Svg {
+            cell_size: 64,
+            render: Render::default(),
+        }
+    }
+}
+
+impl Svg {
+    /// The center of a cell, in image coordinates.
+    pub fn center(&self, board: &Board, pos: u32) -> (f64, f64) {
+        let size = self.cell_size as f64;
+        let width = board.width().max(1);
+        let (row, col) = (pos / width, pos % width);
+        (size * (col as f64 + 0.5), size * (row as f64 + 0.5))
+    }
+
+    pub fn render(&self, board: &Board, paths: &[Path]) -> String {
+        let size = self.cell_size as f64;
+        let (image_width, image_height) =
+            (size * board.width() as f64, size * board.height() as f64);
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+            w = image_width,
+            h = image_height
+        );
+        out.push_str("<defs>\n");
+        for (i, color) in PALETTE.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "<marker id=\"arrow{}\" viewBox=\"0 0 10 10\" refX=\"8\" refY=\"5\" markerWidth=\"4\" markerHeight=\"4\" orient=\"auto-start-reverse\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"{}\"/></marker>",
+                i, color
+            );
+        }
+        out.push_str("</defs>\n");
+
+        let (inset, radius, font) = (size * 0.06, size * 0.12, size * 0.45);
+        for (pos, c) in board.cells() {
+            let (x, y) = self.center(board, pos);
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"#f4ecd8\" stroke=\"#8a7a5a\"/>",
+                x - size / 2.0 + inset,
+                y - size / 2.0 + inset,
+                size - 2.0 * inset,
+                size - 2.0 * inset,
+                radius
+            );
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" font-weight=\"bold\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+                x,
+                y,
+                font,
+                escape(&self.render.tile(board, c))
+            );
+        }
+
+        for (i, path) in paths.iter().enumerate() {
+            let points: Vec<String> = path
+                .iter()
+                .map(|&pos| {
+                    let (x, y) = self.center(board, pos);
+                    format!("{},{}", x, y)
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-opacity=\"0.7\" stroke-linecap=\"round\" stroke-linejoin=\"round\" marker-end=\"url(#arrow{})\"/>",
+                points.join(" "),
+                PALETTE[i % PALETTE.len()],
+                size * 0.08,
+                i % PALETTE.len()
+            );
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// test Svg
+
+#[cfg(test)]
+mod test_svg {
+    use super::*;
+
+    #[test]
+    fn center() {
+        let board: Board = "abc def".parse().unwrap();
+        let svg = Svg::default();
+
+        assert_eq!(svg.center(&board, 0), (32.0, 32.0));
+        assert_eq!(svg.center(&board, 5), (160.0, 96.0));
+    }
+
+    #[test]
+    fn render() {
+        let board: Board = "qat bin".parse().unwrap();
+
+        let image = Svg::default().render(&board, &[vec![0, 1, 2], vec![3, 4]]);
+
+        assert!(image
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"192\" height=\"128\""));
+        assert!(image.ends_with("</svg>\n"));
+        assert_eq!(image.matches("<rect").count(), 6);
+        assert!(image.contains(">Q</text>"));
+        assert!(image.contains("<polyline points=\"32,32 96,32 160,32\""));
+        assert!(image.contains("<polyline points=\"32,96 96,96\" fill=\"none\" stroke=\"#3cb44b\""));
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(escape("a<&>"), "a&lt;&amp;&gt;");
+    }
+}
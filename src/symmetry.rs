@@ -0,0 +1,271 @@
+use crate::{Board, Graph, Path};
+use std::collections::BTreeMap as Map;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+// Symmetry
+
+/// One of the 8 rotations and reflections of a square board.
+///
+/// Each maps the cell at row `r`, column `c` of an `n` by `n` board to:
+///
+/// - `Identity`: `(r, c)`
+/// - `Rotate90`, clockwise: `(c, n-1-r)`
+/// - `Rotate180`: `(n-1-r, n-1-c)`
+/// - `Rotate270`: `(n-1-c, r)`
+/// - `FlipHorizontal`, left to right: `(r, n-1-c)`
+/// - `FlipVertical`, top to bottom: `(n-1-r, c)`
+/// - `Transpose`: `(c, r)`
+/// - `AntiTranspose`: `(n-1-c, n-1-r)`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            other => other,
+        }
+    }
+
+    /// Where the cell at `pos` on a `size` by `size` board moves to.
+    pub fn map_pos(self, size: u32, pos: u32) -> u32 {
+        let (r, c) = (pos / size, pos % size);
+        let last = size - 1;
+        let (row, col) = match self {
+            Symmetry::Identity => (r, c),
+            Symmetry::Rotate90 => (c, last - r),
+            Symmetry::Rotate180 => (last - r, last - c),
+            Symmetry::Rotate270 => (last - c, r),
+            Symmetry::FlipHorizontal => (r, last - c),
+            Symmetry::FlipVertical => (last - r, c),
+            Symmetry::Transpose => (c, r),
+            Symmetry::AntiTranspose => (last - c, last - r),
+        };
+        row * size + col
+    }
+
+    pub fn map_path(self, size: u32, path: &Path) -> Path {
+        path.iter().map(|&pos| self.map_pos(size, pos)).collect()
+    }
+
+    /// The board rotated or reflected, or `None` if it isn't square.
+    pub fn apply(self, board: &Board) -> Option<Board> {
+        if board.width != board.height {
+            return None;
+        }
+        let size = board.width;
+        let mut grid = Graph(Map::new());
+        for (&x, edges) in board.grid.0.iter() {
+            grid.0.entry(self.map_pos(size, x)).or_default();
+            for &y in edges {
+                grid.add_directed_edge(self.map_pos(size, x), self.map_pos(size, y));
+            }
+        }
+        let chars = board
+            .chars
+            .iter()
+            .map(|(&pos, &c)| (self.map_pos(size, pos), c))
+            .collect();
+
+        Some(Board {
+            width: size,
+            height: size,
+            grid,
+            chars,
+            qu_tiles: board.qu_tiles,
+        })
+    }
+
+    /// The symmetry taking `from` to `to`, if they are symmetric.
+    pub fn between(from: &Board, to: &Board) -> Option<Symmetry> {
+        Symmetry::ALL
+            .iter()
+            .copied()
+            .find(|symmetry| symmetry.apply(from).as_ref() == Some(to))
+    }
+}
+
+/// The canonical form of a square board: the least of its 8 symmetric forms,
+/// comparing cells in row-major order. Also returns the symmetry that takes
+/// `board` to it. Returns `None` if the board isn't square.
+pub fn canonical(board: &Board) -> Option<(Board, Symmetry)> {
+    Symmetry::ALL
+        .iter()
+        .filter_map(|&symmetry| symmetry.apply(board).map(|b| (b, symmetry)))
+        .min_by_key(|(b, _)| key(b))
+}
+
+// Row-major cells, then the graph, so that boards compare first by letters.
+fn key(board: &Board) -> (Vec<Option<char>>, Vec<(u32, u32)>) {
+    let cells = (0..board.width * board.height)
+        .map(|pos| board.char_at(pos))
+        .collect();
+    let edges = board
+        .grid
+        .0
+        .iter()
+        .flat_map(|(&x, edges)| edges.iter().map(move |&y| (x, y)))
+        .collect();
+    (cells, edges)
+}
+
+// Canonical
+
+/// A board up to rotation and reflection: two `Canonical`s are equal, and
+/// hash alike, exactly when their boards are symmetric. Boards that aren't
+/// square are only equal to themselves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Canonical(Board);
+
+impl Canonical {
+    pub fn new(board: &Board) -> Canonical {
+        match canonical(board) {
+            Some((board, _)) => Canonical(board),
+            None => Canonical(board.clone()),
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.0
+    }
+}
+
+impl Eq for Canonical {}
+
+impl Hash for Canonical {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.width.hash(state);
+        self.0.height.hash(state);
+        self.0.chars.hash(state);
+        self.0.grid.0.hash(state);
+    }
+}
+
+/// Drops every board symmetric to an earlier one, keeping the order.
+pub fn dedup(boards: Vec<Board>) -> Vec<Board> {
+    let mut seen = HashSet::new();
+    boards
+        .into_iter()
+        .filter(|board| seen.insert(Canonical::new(board)))
+        .collect()
+}
+
+// test Symmetry
+
+#[cfg(test)]
+mod test_symmetry {
+    use super::*;
+    use crate::{Dict, NonLetters, ParseOptions};
+
+    #[test]
+    fn apply() {
+        let board: Board = "abc def ghi".parse().unwrap();
+        let rows = |symmetry: Symmetry| {
+            let board = symmetry.apply(&board).unwrap();
+            (0..9)
+                .map(|pos| board.char_at(pos).unwrap())
+                .collect::<String>()
+        };
+
+        assert_eq!(rows(Symmetry::Identity), "abcdefghi");
+        assert_eq!(rows(Symmetry::Rotate90), "gdahebifc");
+        assert_eq!(rows(Symmetry::Rotate180), "ihgfedcba");
+        assert_eq!(rows(Symmetry::Rotate270), "cfibehadg");
+        assert_eq!(rows(Symmetry::FlipHorizontal), "cbafedihg");
+        assert_eq!(rows(Symmetry::FlipVertical), "ghidefabc");
+        assert_eq!(rows(Symmetry::Transpose), "adgbehcfi");
+        assert_eq!(rows(Symmetry::AntiTranspose), "ifchebgda");
+        assert_eq!(Symmetry::Rotate90.apply(&"abc def".parse().unwrap()), None);
+
+        for &symmetry in Symmetry::ALL.iter() {
+            let there = symmetry.apply(&board).unwrap();
+            assert_eq!(symmetry.inverse().apply(&there).unwrap(), board);
+        }
+    }
+
+    #[test]
+    fn canonical_form() {
+        let board: Board = "xbc def ghi".parse().unwrap();
+
+        for &symmetry in Symmetry::ALL.iter() {
+            let other = symmetry.apply(&board).unwrap();
+            let (form, to_form) = canonical(&other).unwrap();
+            assert_eq!(form, canonical(&board).unwrap().0);
+            assert_eq!(to_form.apply(&other).unwrap(), form);
+            assert_eq!(Canonical::new(&other), Canonical::new(&board));
+        }
+        assert_ne!(
+            Canonical::new(&board),
+            Canonical::new(&"xbc deg fhi".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn map_path() {
+        let dict: Dict = ["bad", "cafe", "hide"].iter().copied().collect();
+        let board: Board = "abc def ghi".parse().unwrap();
+        let turned = Symmetry::Rotate270.apply(&board).unwrap();
+        let symmetry = Symmetry::between(&board, &turned).unwrap();
+
+        let mut mapped: Vec<Path> = board
+            .search(&dict)
+            .iter()
+            .map(|path| symmetry.map_path(3, path))
+            .collect();
+        let mut found = turned.search(&dict);
+        mapped.sort();
+        found.sort();
+
+        assert_eq!(symmetry, Symmetry::Rotate270);
+        assert_eq!(mapped, found);
+        assert_eq!(board.words(&dict), turned.words(&dict));
+    }
+
+    #[test]
+    fn dedup_boards() {
+        let options = ParseOptions {
+            non_letters: NonLetters::Void,
+            ..ParseOptions::default()
+        };
+        let board = Board::parse_with("ab. cde fgh", &options).unwrap();
+        let boards = vec![
+            board.clone(),
+            Symmetry::Rotate90.apply(&board).unwrap(),
+            "abc def ghi".parse().unwrap(),
+            Symmetry::Transpose.apply(&board).unwrap(),
+            "ab cd ef".parse().unwrap(),
+            "ab cd ef".parse().unwrap(),
+        ];
+
+        assert_eq!(
+            dedup(boards),
+            vec![
+                board,
+                "abc def ghi".parse().unwrap(),
+                "ab cd ef".parse().unwrap()
+            ]
+        );
+    }
+}
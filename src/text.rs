@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+
+// Composition table
+//
+// Precomposed letters with the base letter and combining mark they decompose
+// to. This covers the accented letters of the Latin alphabets we ship games
+// for (Spanish, French, German, Polish, Czech, Hungarian, Romanian, Turkish,
+// and a few more); other combining sequences are left as they are. Those are
+// the only languages supported: `Language::load` rejects a pack whose letters
+// need a mark outside this table.
+
+const GRAVE: char = '\u{300}';
+const ACUTE: char = '\u{301}';
+const CIRCUMFLEX: char = '\u{302}';
+const TILDE: char = '\u{303}';
+const DIAERESIS: char = '\u{308}';
+const RING: char = '\u{30a}';
+const CARON: char = '\u{30c}';
+const DOT: char = '\u{307}';
+const CEDILLA: char = '\u{327}';
+const OGONEK: char = '\u{328}';
+const DOUBLE_ACUTE: char = '\u{30b}';
+const BREVE: char = '\u{306}';
+const COMMA: char = '\u{326}';
+
+#[rustfmt::skip]
+const COMPOSED: [(char, char, char); 100] = [
+    ('à', 'a', GRAVE), ('è', 'e', GRAVE), ('ì', 'i', GRAVE), ('ò', 'o', GRAVE), ('ù', 'u', GRAVE),
+    ('À', 'A', GRAVE), ('È', 'E', GRAVE), ('Ì', 'I', GRAVE), ('Ò', 'O', GRAVE), ('Ù', 'U', GRAVE),
+    ('á', 'a', ACUTE), ('é', 'e', ACUTE), ('í', 'i', ACUTE), ('ó', 'o', ACUTE), ('ú', 'u', ACUTE),
+    ('Á', 'A', ACUTE), ('É', 'E', ACUTE), ('Í', 'I', ACUTE), ('Ó', 'O', ACUTE), ('Ú', 'U', ACUTE),
+    ('ý', 'y', ACUTE), ('ć', 'c', ACUTE), ('ń', 'n', ACUTE), ('ś', 's', ACUTE), ('ź', 'z', ACUTE),
+    ('Ý', 'Y', ACUTE), ('Ć', 'C', ACUTE), ('Ń', 'N', ACUTE), ('Ś', 'S', ACUTE), ('Ź', 'Z', ACUTE),
+    ('â', 'a', CIRCUMFLEX), ('ê', 'e', CIRCUMFLEX), ('î', 'i', CIRCUMFLEX), ('ô', 'o', CIRCUMFLEX),
+    ('û', 'u', CIRCUMFLEX), ('Â', 'A', CIRCUMFLEX), ('Ê', 'E', CIRCUMFLEX), ('Î', 'I', CIRCUMFLEX),
+    ('Ô', 'O', CIRCUMFLEX), ('Û', 'U', CIRCUMFLEX),
+    ('ã', 'a', TILDE), ('ñ', 'n', TILDE), ('õ', 'o', TILDE),
+    ('Ã', 'A', TILDE), ('Ñ', 'N', TILDE), ('Õ', 'O', TILDE),
+    ('ä', 'a', DIAERESIS), ('ë', 'e', DIAERESIS), ('ï', 'i', DIAERESIS), ('ö', 'o', DIAERESIS),
+    ('ü', 'u', DIAERESIS), ('ÿ', 'y', DIAERESIS), ('Ä', 'A', DIAERESIS), ('Ë', 'E', DIAERESIS),
+    ('Ï', 'I', DIAERESIS), ('Ö', 'O', DIAERESIS), ('Ü', 'U', DIAERESIS), ('Ÿ', 'Y', DIAERESIS),
+    ('å', 'a', RING), ('Å', 'A', RING),
+    ('č', 'c', CARON), ('ě', 'e', CARON), ('ř', 'r', CARON), ('š', 's', CARON), ('ž', 'z', CARON),
+    ('Č', 'C', CARON), ('Ě', 'E', CARON), ('Ř', 'R', CARON), ('Š', 'S', CARON), ('Ž', 'Z', CARON),
+    ('ż', 'z', DOT), ('Ż', 'Z', DOT),
+    ('ç', 'c', CEDILLA), ('Ç', 'C', CEDILLA), ('ş', 's', CEDILLA), ('Ş', 'S', CEDILLA),
+    ('ą', 'a', OGONEK), ('ę', 'e', OGONEK), ('Ą', 'A', OGONEK), ('Ę', 'E', OGONEK),
+    ('ů', 'u', RING), ('Ů', 'U', RING), ('ň', 'n', CARON), ('Ň', 'N', CARON),
+    ('ď', 'd', CARON), ('Ď', 'D', CARON), ('ť', 't', CARON), ('Ť', 'T', CARON),
+    ('ő', 'o', DOUBLE_ACUTE), ('ű', 'u', DOUBLE_ACUTE), ('Ő', 'O', DOUBLE_ACUTE), ('Ű', 'U', DOUBLE_ACUTE),
+    ('ă', 'a', BREVE), ('ğ', 'g', BREVE), ('Ă', 'A', BREVE), ('Ğ', 'G', BREVE),
+    ('ș', 's', COMMA), ('ț', 't', COMMA), ('Ș', 'S', COMMA), ('Ț', 'T', COMMA),
+];
+
+// Letters whose accents are not combining marks, and so do not decompose.
+const STROKED: [(char, char); 4] = [('ł', 'l'), ('Ł', 'L'), ('ø', 'o'), ('Ø', 'O')];
+
+pub fn is_combining_mark(c: char) -> bool {
+    ('\u{300}'..='\u{36f}').contains(&c)
+}
+
+fn compose(base: char, mark: char) -> Option<char> {
+    COMPOSED
+        .iter()
+        .find(|&&(_, b, m)| b == base && m == mark)
+        .map(|&(c, _, _)| c)
+}
+
+/// Composes a letter followed by a combining mark into a single character,
+/// as NFC does, but only for the Latin letters in the composition table.
+/// This is not full Unicode normalization: any other letter and mark, such
+/// as a Vietnamese letter with two accents, stays decomposed. Text without
+/// combining marks is returned as is.
+pub fn compose_latin(s: &str) -> Cow<'_, str> {
+    if !s.chars().any(is_combining_mark) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        let composed = match out.chars().last() {
+            Some(base) if is_combining_mark(c) => compose(base, c).map(|x| (base, x)),
+            _ => None,
+        };
+        match composed {
+            Some((base, x)) => {
+                out.truncate(out.len() - base.len_utf8());
+                out.push(x);
+            }
+            None => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// The base letter of an accented letter, or `c` itself.
+pub fn strip_accent(c: char) -> char {
+    COMPOSED
+        .iter()
+        .find(|&&(x, _, _)| x == c)
+        .map(|&(_, base, _)| base)
+        .or_else(|| {
+            STROKED
+                .iter()
+                .find(|&&(x, _)| x == c)
+                .map(|&(_, base)| base)
+        })
+        .unwrap_or(c)
+}
+
+/// Composes `s` and removes the accents from its letters, for matching
+/// words without regard to accents.
+pub fn strip_accents(s: &str) -> String {
+    compose_latin(s)
+        .chars()
+        .filter(|&c| !is_combining_mark(c))
+        .map(strip_accent)
+        .collect()
+}
+
+// test text
+
+#[cfg(test)]
+mod test_text {
+    use super::*;
+
+    #[test]
+    fn composes() {
+        assert_eq!(compose_latin("e\u{301}te\u{301}"), "été");
+        assert_eq!(compose_latin("Z\u{307}o\u{301}\u{142}w"), "Żółw");
+        assert_eq!(compose_latin("o\u{30b}s\u{326}"), "őș");
+        assert_eq!(compose_latin("x\u{301}"), "x\u{301}");
+    }
+
+    #[test]
+    fn borrows() {
+        assert!(matches!(compose_latin("ñandú"), Cow::Borrowed("ñandú")));
+    }
+
+    #[test]
+    fn strip() {
+        assert_eq!(strip_accents("Übergröße"), "Ubergroße");
+        assert_eq!(strip_accents("żółw"), "zolw");
+        assert_eq!(strip_accents("e\u{301}te\u{301}"), "ete");
+        assert_eq!(strip_accent('ç'), 'c');
+        assert_eq!(strip_accent('q'), 'q');
+    }
+}
@@ -0,0 +1,53 @@
+// Builds the staticlib, compiles tests/ffi/test.c against it and runs it.
+// Skipped when there is no C compiler.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn c_harness() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let tmp = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    // the library is built for the host, in a target directory of its own, so
+    // its path doesn't depend on the --target or --profile of this test run
+    let target_dir = tmp.join("ffi-target");
+    let lib = target_dir.join("debug").join("libwordsearch.a");
+    let out = tmp.join("ffi-test");
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    if Command::new(&cc).arg("--version").output().is_err() {
+        eprintln!("skipping: no C compiler ({})", cc);
+        return;
+    }
+
+    // `cargo test` only builds the rlib, so build the staticlib too
+    let status = Command::new(env!("CARGO"))
+        .current_dir(&root)
+        .args(["build", "--lib", "--target-dir"])
+        .arg(&target_dir)
+        .env_remove("CARGO_BUILD_TARGET")
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the library failed");
+    assert!(lib.exists(), "{} not built", lib.display());
+
+    let status = Command::new(&cc)
+        .arg(root.join("tests/ffi/test.c"))
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg(&lib)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&out)
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling the C harness failed");
+
+    let output = Command::new(&out).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
@@ -0,0 +1,62 @@
+/* Exercises the C interface. Build against the staticlib, e.g.
+ *
+ *   cargo build
+ *   cc tests/ffi/test.c -Iinclude target/debug/libwordsearch.a \
+ *       -lpthread -ldl -lm -o target/ffi-test && target/ffi-test
+ *
+ * tests/ffi.rs does this as part of `cargo test`. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "wordsearch.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                   \
+    do {                                                              \
+        if (!(cond)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,    \
+                    __LINE__, #cond);                                 \
+            failures++;                                               \
+        }                                                             \
+    } while (0)
+
+int main(void) {
+    const char *words = "an\nant\nbat\ntab\n";
+    char error[64];
+    uint32_t cells[8];
+    size_t i, n, found_bat = 0;
+
+    ws_dict *dict = ws_dict_new(words, strlen(words));
+    ws_board *board = ws_board_parse("ba tn", error, sizeof error);
+    CHECK(dict != NULL);
+    CHECK(board != NULL);
+
+    ws_results *results = ws_search(board, dict);
+    CHECK(ws_results_len(results) == 4);
+
+    for (i = 0; i < ws_results_len(results); i++) {
+        if (strcmp(ws_results_word(results, i), "bat") == 0) {
+            n = ws_results_path(results, i, cells, 8);
+            CHECK(n == 3);
+            CHECK(cells[0] == 0 && cells[1] == 1 && cells[2] == 2);
+            found_bat = 1;
+        }
+    }
+    CHECK(found_bat);
+    CHECK(ws_results_word(results, 4) == NULL);
+
+    ws_results_free(results);
+    ws_board_free(board);
+
+    CHECK(ws_board_parse("abc", error, sizeof error) == NULL);
+    CHECK(strcmp(error, "must have at least two rows, found 1") == 0);
+
+    ws_dict_free(dict);
+
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures == 0 ? 0 : 1;
+}